@@ -34,7 +34,7 @@ struct EventHandlerImpl {
 }
 
 impl EventHandler<Event> for EventHandlerImpl {
-    fn on_event(&mut self, event: &mut Event, sequence: i64, end_of_batch: bool) {
+    fn on_event(&mut self, event: &mut Event, sequence: i64, _end_of_batch: bool) {
         println!(
             "{} processed event: {} at sequence {}",
             self.name, event.value, sequence
@@ -25,7 +25,7 @@ impl EventFactory<MyEvent> for MyFactory {
 struct MyHandler;
 
 impl EventHandler<MyEvent> for MyHandler {
-    fn on_event(&mut self, event: &mut MyEvent, sequence: i64, end_of_batch: bool) {
+    fn on_event(&mut self, event: &mut MyEvent, sequence: i64, _end_of_batch: bool) {
         println!(
             "MyHandler processing: {} at sequence {}",
             event.data, sequence
@@ -37,7 +37,7 @@ impl EventHandler<MyEvent> for MyHandler {
 struct AnotherHandler;
 
 impl EventHandler<MyEvent> for AnotherHandler {
-    fn on_event(&mut self, event: &mut MyEvent, sequence: i64, end_of_batch: bool) {
+    fn on_event(&mut self, event: &mut MyEvent, sequence: i64, _end_of_batch: bool) {
         println!(
             "AnotherHandler processing: {} at sequence {}",
             event.data, sequence
@@ -49,7 +49,7 @@ impl EventHandler<MyEvent> for AnotherHandler {
 struct NextHandler;
 
 impl EventHandler<MyEvent> for NextHandler {
-    fn on_event(&mut self, event: &mut MyEvent, sequence: i64, end_of_batch: bool) {
+    fn on_event(&mut self, event: &mut MyEvent, sequence: i64, _end_of_batch: bool) {
         println!(
             "NextHandler processing: {} at sequence {}",
             event.data, sequence
@@ -59,7 +59,7 @@ impl EventHandler<MyEvent> for NextHandler {
 
 fn main() {
     // Multi producer example with parallel processing
-    let (mut disruptor1, producer1) = DisruptorBuilder::new_multi_producer(
+    let (_disruptor1, _producer1) = DisruptorBuilder::new_multi_producer(
         1024,
         MyFactory,
         TokioExecutor::new(4),
@@ -70,7 +70,7 @@ fn main() {
     .build_with_producer();
 
     // Multi producer example with sequential processing
-    let (mut disruptor2, producer2) = DisruptorBuilder::new_multi_producer(
+    let (_disruptor2, _producer2) = DisruptorBuilder::new_multi_producer(
         1024,
         MyFactory,
         TokioExecutor::new(4),
@@ -25,7 +25,7 @@ impl EventFactory<MyEvent> for MyFactory {
 struct Stage1Handler;
 
 impl EventHandler<MyEvent> for Stage1Handler {
-    fn on_event(&mut self, event: &mut MyEvent, sequence: i64, end_of_batch: bool) {
+    fn on_event(&mut self, event: &mut MyEvent, sequence: i64, _end_of_batch: bool) {
         event.processed_by.push("Stage1".to_string());
         println!(
             "Stage1 processed event {} at sequence {}",
@@ -38,7 +38,7 @@ impl EventHandler<MyEvent> for Stage1Handler {
 struct Stage2Handler;
 
 impl EventHandler<MyEvent> for Stage2Handler {
-    fn on_event(&mut self, event: &mut MyEvent, sequence: i64, end_of_batch: bool) {
+    fn on_event(&mut self, event: &mut MyEvent, sequence: i64, _end_of_batch: bool) {
         event.processed_by.push("Stage2".to_string());
         println!(
             "Stage2 processed event {} at sequence {}",
@@ -51,7 +51,7 @@ impl EventHandler<MyEvent> for Stage2Handler {
 struct Stage3Handler;
 
 impl EventHandler<MyEvent> for Stage3Handler {
-    fn on_event(&mut self, event: &mut MyEvent, sequence: i64, end_of_batch: bool) {
+    fn on_event(&mut self, event: &mut MyEvent, sequence: i64, _end_of_batch: bool) {
         event.processed_by.push("Stage3".to_string());
         println!(
             "Stage3 processed event {} at sequence {} (after Stage1+2)",
@@ -64,7 +64,7 @@ impl EventHandler<MyEvent> for Stage3Handler {
 struct Stage4Handler;
 
 impl EventHandler<MyEvent> for Stage4Handler {
-    fn on_event(&mut self, event: &mut MyEvent, sequence: i64, end_of_batch: bool) {
+    fn on_event(&mut self, event: &mut MyEvent, sequence: i64, _end_of_batch: bool) {
         event.processed_by.push("Stage4".to_string());
         println!(
             "Stage4 processed event {} at sequence {} (parallel with Stage3)",
@@ -74,7 +74,7 @@ impl EventHandler<MyEvent> for Stage4Handler {
 }
 
 fn main() {
-    let (mut disruptor, producer) = DisruptorBuilder::new_multi_producer(
+    let (_disruptor, _producer) = DisruptorBuilder::new_multi_producer(
         1024,
         MyFactory,
         TokioExecutor::new(4),
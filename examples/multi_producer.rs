@@ -25,14 +25,14 @@ impl EventFactory<MyEvent> for MyFactory {
 struct MyHandler;
 
 impl EventHandler<MyEvent> for MyHandler {
-    fn on_event(&mut self, event: &mut MyEvent, sequence: i64, end_of_batch: bool) {
+    fn on_event(&mut self, event: &mut MyEvent, sequence: i64, _end_of_batch: bool) {
         println!("Processing event {} at sequence {}", event.value, sequence);
     }
 }
 
 fn main() {
     // Multi producer with BusySpin wait strategy
-    let (mut disruptor1, producer1) = DisruptorBuilder::new_multi_producer(
+    let (_disruptor1, _producer1) = DisruptorBuilder::new_multi_producer(
         2048,
         MyFactory,
         TokioExecutor::new(4),
@@ -42,7 +42,7 @@ fn main() {
     .build_with_producer();
 
     // Multi producer with Yielding wait strategy
-    let (mut disruptor2, producer2) = DisruptorBuilder::new_multi_producer(
+    let (_disruptor2, _producer2) = DisruptorBuilder::new_multi_producer(
         1024,
         MyFactory,
         TokioExecutor::new(2),
@@ -27,7 +27,7 @@ struct HandlerA {
 }
 
 impl EventHandler<Event> for HandlerA {
-    fn on_event(&mut self, event: &mut Event, sequence: i64, end_of_batch: bool) {
+    fn on_event(&mut self, event: &mut Event, _sequence: i64, _end_of_batch: bool) {
         println!("{} processing event {}", self.name, event.id);
     }
 }
@@ -37,7 +37,7 @@ struct HandlerB {
 }
 
 impl EventHandler<Event> for HandlerB {
-    fn on_event(&mut self, event: &mut Event, sequence: i64, end_of_batch: bool) {
+    fn on_event(&mut self, event: &mut Event, _sequence: i64, _end_of_batch: bool) {
         println!("{} processing after HandlerA: {}", self.name, event.id);
     }
 }
@@ -48,7 +48,7 @@ struct HandlerC {
 }
 
 impl EventHandler<Event> for HandlerC {
-    fn on_event(&mut self, event: &mut Event, sequence: i64, end_of_batch: bool) {
+    fn on_event(&mut self, event: &mut Event, _sequence: i64, _end_of_batch: bool) {
         println!("{} processing event {}", self.name, event.id);
     }
 }
@@ -58,7 +58,7 @@ struct HandlerD {
 }
 
 impl EventHandler<Event> for HandlerD {
-    fn on_event(&mut self, event: &mut Event, sequence: i64, end_of_batch: bool) {
+    fn on_event(&mut self, event: &mut Event, _sequence: i64, _end_of_batch: bool) {
         println!("{} processing after HandlerC: {}", self.name, event.id);
     }
 }
@@ -7,14 +7,29 @@ use crate::{EventProcessor, Runnable, Sequencer, SharedRef};
 
 pub struct EventProcessorAdapter {
     processor: SharedRef<Box<dyn EventProcessor>>,
+    name: Option<(String, usize)>,
 }
 
 impl EventProcessorAdapter {
     pub fn new(processor: Box<dyn EventProcessor>) -> Self {
         EventProcessorAdapter {
             processor: SharedRef::new(processor),
+            name: None,
         }
     }
+
+    pub fn named(processor: Box<dyn EventProcessor>, name: &str, index: usize) -> Self {
+        EventProcessorAdapter {
+            processor: SharedRef::new(processor),
+            name: Some((name.to_string(), index)),
+        }
+    }
+
+    pub fn name(&self) -> Option<(&str, usize)> {
+        self.name
+            .as_ref()
+            .map(|(name, index)| (name.as_str(), *index))
+    }
 }
 
 impl Runnable for EventProcessorAdapter {
@@ -27,6 +42,7 @@ impl Clone for EventProcessorAdapter {
     fn clone(&self) -> Self {
         Self {
             processor: self.processor.clone(),
+            name: self.name.clone(),
         }
     }
 }
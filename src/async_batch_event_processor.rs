@@ -0,0 +1,385 @@
+use std::{
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicU8, Ordering},
+    },
+};
+
+use crate::{
+    AsyncEventHandler, EventProcessor, ExceptionAction, ExceptionHandler, RingBuffer, Runnable,
+    STATE_HALTED, STATE_IDLE, STATE_RUNNING, Sequence, SequenceBarrier, WaitError,
+    exception_handler::{HaltGroup, HaltProcessorExceptionHandler},
+    util,
+};
+
+#[cfg(feature = "latency")]
+use crate::latency::LatencyStage;
+#[cfg(feature = "metrics")]
+use crate::metrics::ProcessorMetrics;
+
+pub struct AsyncBatchEventProcessor<E, B, H>
+where
+    B: SequenceBarrier,
+    H: AsyncEventHandler<E>,
+{
+    sequence: Arc<Sequence>,
+    event_handler: H,
+    ringbuffer: Arc<RingBuffer<E>>,
+    sequence_barrier: Arc<B>,
+    state: Arc<AtomicU8>,
+    #[cfg(feature = "latency")]
+    latency: LatencyStage,
+    #[cfg(feature = "metrics")]
+    metrics: Arc<ProcessorMetrics>,
+    exception_handler: Arc<dyn ExceptionHandler<E>>,
+    halt_group: HaltGroup,
+}
+
+unsafe impl<E, B, H> Send for AsyncBatchEventProcessor<E, B, H>
+where
+    B: SequenceBarrier,
+    H: AsyncEventHandler<E>,
+{
+}
+
+unsafe impl<E, B, H> Sync for AsyncBatchEventProcessor<E, B, H>
+where
+    B: SequenceBarrier,
+    H: AsyncEventHandler<E>,
+{
+}
+
+impl<E, B, H> AsyncBatchEventProcessor<E, B, H>
+where
+    B: SequenceBarrier,
+    H: AsyncEventHandler<E>,
+    E: Send + Sync + 'static,
+{
+    pub fn new(event_handler: H, ringbuffer: Arc<RingBuffer<E>>, sequence_barrier: Arc<B>) -> Self {
+        AsyncBatchEventProcessor {
+            sequence: Arc::new(Sequence::new()),
+            event_handler,
+            ringbuffer,
+            sequence_barrier,
+            state: Arc::new(AtomicU8::new(STATE_IDLE)),
+            #[cfg(feature = "latency")]
+            latency: LatencyStage::default(),
+            #[cfg(feature = "metrics")]
+            metrics: Arc::new(ProcessorMetrics::new()),
+            exception_handler: Arc::new(HaltProcessorExceptionHandler),
+            halt_group: HaltGroup::new(),
+        }
+    }
+
+    pub fn set_exception_handler(&mut self, exception_handler: Arc<dyn ExceptionHandler<E>>) {
+        self.exception_handler = exception_handler;
+    }
+
+    #[cfg(feature = "latency")]
+    pub(crate) fn trace_latency(&mut self, latency: LatencyStage) {
+        self.latency = latency;
+    }
+
+    pub(crate) fn join_halt_group(&mut self, halt_group: HaltGroup)
+    where
+        B: 'static,
+    {
+        let state = self.state.clone();
+        let sequence_barrier = self.sequence_barrier.clone();
+        halt_group.add(move || {
+            state.store(STATE_HALTED, Ordering::Release);
+            sequence_barrier.alert();
+        });
+        self.halt_group = halt_group;
+    }
+
+    async fn notify_start(&mut self) {
+        if let Err(error) = util::catch_unwind(self.event_handler.on_start()).await {
+            let action = self.exception_handler.handle_on_start_exception(&*error);
+            self.handle_exception(action);
+        }
+    }
+
+    async fn notify_shutdown(&mut self) {
+        if let Err(error) = util::catch_unwind(self.event_handler.on_shutdown()).await {
+            self.exception_handler.handle_on_shutdown_exception(&*error);
+        }
+    }
+
+    async fn notify_timeout(&mut self) {
+        let sequence = self.sequence.get();
+        if let Err(error) = util::catch_unwind(self.event_handler.on_timeout(sequence)).await {
+            let action = self
+                .exception_handler
+                .handle_on_timeout_exception(&*error, sequence);
+            self.handle_exception(action);
+        }
+    }
+
+    fn handle_exception(&mut self, action: ExceptionAction) {
+        match action {
+            ExceptionAction::Continue => {}
+            ExceptionAction::HaltProcessor => self.halt(),
+            ExceptionAction::HaltDisruptor => {
+                self.halt();
+                self.halt_group.halt_all();
+            }
+        }
+    }
+
+    async fn process_events(&mut self) {
+        let mut next_sequence = self.sequence.get() + 1;
+
+        match self.sequence_barrier.wait_for_async(next_sequence).await {
+            Ok(available_sequence) => {
+                let batch_start = next_sequence;
+                if available_sequence >= batch_start {
+                    let batch_size = available_sequence - batch_start + 1;
+                    let result = util::catch_unwind(
+                        self.event_handler.on_batch_start(batch_size, batch_size),
+                    )
+                    .await;
+                    if let Err(error) = result {
+                        // Reported against the first event of the batch, which
+                        // is still unprocessed if the handler decides to halt.
+                        let event = self.ringbuffer.get_mut(batch_start as usize);
+                        let action = self.exception_handler.handle_event_exception(
+                            &*error,
+                            batch_start,
+                            event,
+                        );
+                        if action != ExceptionAction::Continue {
+                            self.handle_exception(action);
+                            return;
+                        }
+                    }
+                }
+
+                while next_sequence <= available_sequence {
+                    let event = self.ringbuffer.get_mut(next_sequence as usize);
+                    let end_of_batch = next_sequence == available_sequence;
+                    let result = util::catch_unwind(self.event_handler.on_event(
+                        event,
+                        next_sequence,
+                        end_of_batch,
+                    ))
+                    .await;
+
+                    #[cfg(feature = "latency")]
+                    if result.is_ok() {
+                        self.latency.record(next_sequence);
+                    }
+
+                    if let Err(error) = result {
+                        let action = self.exception_handler.handle_event_exception(
+                            &*error,
+                            next_sequence,
+                            event,
+                        );
+                        if action != ExceptionAction::Continue {
+                            self.sequence.set(next_sequence - 1);
+                            self.sequence_barrier.signal_sequence_advanced();
+                            self.handle_exception(action);
+                            return;
+                        }
+                    }
+
+                    // Advance per event so a slow await doesn't hold the whole
+                    // batch back from downstream stages.
+                    self.sequence.set(next_sequence);
+                    self.sequence_barrier.signal_sequence_advanced();
+                    next_sequence += 1;
+                }
+
+                #[cfg(feature = "metrics")]
+                if next_sequence > batch_start {
+                    self.metrics.record_batch(next_sequence - batch_start);
+                }
+            }
+            Err(WaitError::Timeout) => self.notify_timeout().await,
+            Err(WaitError::Alerted) => {}
+        }
+    }
+}
+
+impl<E, B, H> Runnable for AsyncBatchEventProcessor<E, B, H>
+where
+    B: SequenceBarrier,
+    H: AsyncEventHandler<E>,
+    E: Send + Sync + 'static,
+{
+    fn run(&mut self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(async move {
+            if self
+                .state
+                .compare_exchange(
+                    STATE_IDLE,
+                    STATE_RUNNING,
+                    Ordering::AcqRel,
+                    Ordering::Relaxed,
+                )
+                .is_ok()
+            {
+                self.sequence_barrier.clear_alert();
+                self.notify_start().await;
+
+                while self.state.load(Ordering::Acquire) == STATE_RUNNING {
+                    self.process_events().await;
+                }
+
+                self.notify_shutdown().await;
+                self.state.store(STATE_IDLE, Ordering::Release);
+            }
+        })
+    }
+}
+
+impl<E, B, H> EventProcessor for AsyncBatchEventProcessor<E, B, H>
+where
+    B: SequenceBarrier,
+    H: AsyncEventHandler<E>,
+    E: Send + Sync + 'static,
+{
+    fn halt(&mut self) {
+        self.state.store(STATE_HALTED, Ordering::Release);
+        self.sequence_barrier.alert();
+    }
+
+    fn sequence(&self) -> Arc<Sequence> {
+        self.sequence.clone()
+    }
+
+    fn running(self) -> bool {
+        self.state.load(Ordering::Acquire) == STATE_RUNNING
+    }
+
+    #[cfg(feature = "metrics")]
+    fn metrics(&self) -> Option<Arc<ProcessorMetrics>> {
+        Some(self.metrics.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Mutex, atomic::AtomicI64},
+        time::Duration,
+    };
+
+    use super::*;
+    use crate::{
+        DisruptorBuilder,
+        exception_handler::LogAndSkipExceptionHandler,
+        executor::TokioExecutor,
+        test_support::{SHUTDOWN_TIMEOUT, SumHandler, TestFactory},
+        wait_strategy::{AsyncWaitStrategy, TimeoutBlockingWaitStrategy},
+    };
+
+    struct YieldingHandler {
+        seen: Arc<Mutex<Vec<i64>>>,
+    }
+
+    impl AsyncEventHandler<i64> for YieldingHandler {
+        async fn on_event(&mut self, event: &mut i64, _sequence: i64, _end_of_batch: bool) {
+            tokio::task::yield_now().await;
+            *event *= 10;
+            self.seen.lock().unwrap().push(*event);
+        }
+    }
+
+    #[test]
+    fn test_async_handler_runs_before_dependents() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sum = Arc::new(AtomicI64::new(0));
+        let (mut disruptor, mut producer) = DisruptorBuilder::new_single_producer(
+            8,
+            TestFactory,
+            TokioExecutor::new(2),
+            AsyncWaitStrategy::new(),
+        )
+        .handler_async(YieldingHandler { seen: seen.clone() })
+        .then(SumHandler { sum: sum.clone() })
+        .build_with_producer();
+
+        disruptor.start();
+        for i in 1..=4 {
+            producer.publish(|e| *e = i);
+        }
+        assert!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT).is_ok());
+
+        assert_eq!(*seen.lock().unwrap(), vec![10, 20, 30, 40]);
+        assert_eq!(sum.load(Ordering::Relaxed), 100);
+    }
+
+    struct PanickingBatchStartHandler {
+        events: Arc<Mutex<Vec<i64>>>,
+    }
+
+    impl AsyncEventHandler<i64> for PanickingBatchStartHandler {
+        async fn on_event(&mut self, _event: &mut i64, sequence: i64, _end_of_batch: bool) {
+            self.events.lock().unwrap().push(sequence);
+        }
+
+        async fn on_batch_start(&mut self, _batch_size: i64, _queue_depth: i64) {
+            panic!("on_batch_start failed");
+        }
+    }
+
+    #[test]
+    fn test_batch_start_panic_goes_to_exception_handler() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let (mut disruptor, mut producer) = DisruptorBuilder::new_single_producer(
+            8,
+            TestFactory,
+            TokioExecutor::new(1),
+            AsyncWaitStrategy::new(),
+        )
+        .with_exception_handler(LogAndSkipExceptionHandler)
+        .handler_async(PanickingBatchStartHandler {
+            events: events.clone(),
+        })
+        .build_with_producer();
+
+        disruptor.start();
+        producer.publish_batch(3, |_, e| *e = 1).unwrap();
+        assert!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT).is_ok());
+        assert_eq!(*events.lock().unwrap(), vec![0, 1, 2]);
+    }
+
+    struct TimeoutHandler {
+        timeouts: Arc<Mutex<Vec<i64>>>,
+    }
+
+    impl AsyncEventHandler<i64> for TimeoutHandler {
+        async fn on_event(&mut self, _event: &mut i64, _sequence: i64, _end_of_batch: bool) {}
+
+        async fn on_timeout(&mut self, sequence: i64) {
+            self.timeouts.lock().unwrap().push(sequence);
+        }
+    }
+
+    #[test]
+    fn test_timeout_reaches_async_handler() {
+        let timeouts = Arc::new(Mutex::new(Vec::new()));
+        let (mut disruptor, mut producer) = DisruptorBuilder::new_single_producer(
+            8,
+            TestFactory,
+            TokioExecutor::new(1),
+            TimeoutBlockingWaitStrategy::new(Duration::from_millis(5)),
+        )
+        .handler_async(TimeoutHandler {
+            timeouts: timeouts.clone(),
+        })
+        .build_with_producer();
+
+        disruptor.start();
+        producer.publish(|e| *e = 1);
+        while !timeouts.lock().unwrap().contains(&0) {
+            std::thread::yield_now();
+        }
+        disruptor.stop();
+
+        assert!(timeouts.lock().unwrap().iter().all(|&s| s <= 0));
+    }
+}
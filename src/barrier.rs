@@ -3,7 +3,7 @@ use std::sync::{
     atomic::{AtomicBool, Ordering},
 };
 
-use crate::{Sequence, SequenceBarrier, Sequencer, SequencerAdapter, WaitStrategy};
+use crate::{Sequence, SequenceBarrier, Sequencer, SequencerAdapter, WaitError, WaitStrategy};
 
 pub struct ProcessingSequenceBarrier<W, S>
 where
@@ -39,7 +39,7 @@ where
         sequencer: SequencerAdapter<S>,
         mut dependent_sequence: Vec<Arc<Sequence>>,
     ) -> Self {
-        if dependent_sequence.len() == 0 {
+        if dependent_sequence.is_empty() {
             dependent_sequence.push(sequencer.cursor());
         }
         ProcessingSequenceBarrier {
@@ -51,29 +51,51 @@ where
     }
 }
 
+// Shared with `EventPoller`, which reads its dependents without waiting.
+pub(crate) fn highest_available<S: Sequencer>(
+    sequencer: &SequencerAdapter<S>,
+    sequence: i64,
+    available_sequence: i64,
+) -> i64 {
+    if available_sequence < sequence {
+        return available_sequence;
+    }
+
+    sequencer.highest_published(sequence, available_sequence)
+}
+
 impl<W, S> SequenceBarrier for ProcessingSequenceBarrier<W, S>
 where
     W: WaitStrategy + Send + Sync,
     S: Sequencer,
 {
-    fn wait_for(&self, sequence: i64) -> Option<i64> {
-        if let Some(available_sequence) = self.wait_strategy.wait_for(
+    fn wait_for(&self, sequence: i64) -> Result<i64, WaitError> {
+        let available_sequence = self.wait_strategy.wait_for(
             sequence,
             self.sequencer.cursor(),
             self.dependent_sequence.clone(),
             || self.alert.load(Ordering::Acquire),
-        ) {
-            if available_sequence < sequence {
-                return Some(available_sequence);
-            }
+        )?;
 
-            return Some(
-                self.sequencer
-                    .highest_published(sequence, available_sequence),
-            );
-        } else {
-            None
-        }
+        Ok(highest_available(
+            &self.sequencer,
+            sequence,
+            available_sequence,
+        ))
+    }
+
+    async fn wait_for_async(&self, sequence: i64) -> Result<i64, WaitError> {
+        self.wait_strategy
+            .wait_for_async(
+                sequence,
+                self.sequencer.cursor(),
+                self.dependent_sequence.clone(),
+                || self.alert.load(Ordering::Acquire),
+                |available_sequence| {
+                    highest_available(&self.sequencer, sequence, available_sequence)
+                },
+            )
+            .await
     }
 
     fn alert(&self) {
@@ -84,4 +106,8 @@ where
     fn clear_alert(&self) {
         self.alert.store(false, Ordering::Release);
     }
+
+    fn signal_sequence_advanced(&self) {
+        self.wait_strategy.signal_sequence_advanced();
+    }
 }
@@ -1,16 +1,29 @@
 use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
     pin::Pin,
     sync::{
         Arc,
         atomic::{AtomicU8, Ordering},
     },
+    time::Duration,
 };
 
+use futures_timer::Delay;
+
 use crate::{
-    EventHandler, EventProcessor, RingBuffer, Runnable, STATE_HALTED, STATE_IDLE, STATE_RUNNING,
-    Sequence, SequenceBarrier,
+    BatchRewindStrategy, EventHandler, EventProcessor, ExceptionAction, ExceptionHandler,
+    RewindAction, RingBuffer, Runnable, STATE_HALTED, STATE_IDLE, STATE_RUNNING, Sequence,
+    SequenceBarrier, WaitError,
+    exception_handler::{HaltGroup, HaltProcessorExceptionHandler},
+    rewind_strategy::SimpleBatchRewindStrategy,
 };
 
+#[cfg(feature = "latency")]
+use crate::latency::LatencyStage;
+#[cfg(feature = "metrics")]
+use crate::metrics::ProcessorMetrics;
+
 pub struct BatchEventProcessor<E, B, H>
 where
     B: SequenceBarrier,
@@ -20,7 +33,14 @@ where
     event_handler: H,
     ringbuffer: Arc<RingBuffer<E>>,
     sequence_barrier: Arc<B>,
-    state: AtomicU8,
+    state: Arc<AtomicU8>,
+    #[cfg(feature = "latency")]
+    latency: LatencyStage,
+    #[cfg(feature = "metrics")]
+    metrics: Arc<ProcessorMetrics>,
+    exception_handler: Arc<dyn ExceptionHandler<E>>,
+    rewind_strategy: Arc<dyn BatchRewindStrategy>,
+    halt_group: HaltGroup,
 }
 
 unsafe impl<E, B, H> Send for BatchEventProcessor<E, B, H>
@@ -41,6 +61,7 @@ impl<E, B, H> BatchEventProcessor<E, B, H>
 where
     B: SequenceBarrier,
     H: EventHandler<E>,
+    E: 'static,
 {
     pub fn new(event_handler: H, ringbuffer: Arc<RingBuffer<E>>, sequence_barrier: Arc<B>) -> Self {
         BatchEventProcessor {
@@ -48,24 +69,182 @@ where
             event_handler,
             ringbuffer,
             sequence_barrier,
-            state: AtomicU8::new(STATE_IDLE),
+            state: Arc::new(AtomicU8::new(STATE_IDLE)),
+            #[cfg(feature = "latency")]
+            latency: LatencyStage::default(),
+            #[cfg(feature = "metrics")]
+            metrics: Arc::new(ProcessorMetrics::new()),
+            exception_handler: Arc::new(HaltProcessorExceptionHandler),
+            rewind_strategy: Arc::new(SimpleBatchRewindStrategy),
+            halt_group: HaltGroup::new(),
+        }
+    }
+
+    pub fn set_exception_handler(&mut self, exception_handler: Arc<dyn ExceptionHandler<E>>) {
+        self.exception_handler = exception_handler;
+    }
+
+    pub fn set_rewind_strategy(&mut self, rewind_strategy: Arc<dyn BatchRewindStrategy>) {
+        self.rewind_strategy = rewind_strategy;
+    }
+
+    #[cfg(feature = "latency")]
+    pub(crate) fn trace_latency(&mut self, latency: LatencyStage) {
+        self.latency = latency;
+    }
+
+    pub(crate) fn join_halt_group(&mut self, halt_group: HaltGroup)
+    where
+        B: 'static,
+    {
+        let state = self.state.clone();
+        let sequence_barrier = self.sequence_barrier.clone();
+        halt_group.add(move || {
+            state.store(STATE_HALTED, Ordering::Release);
+            sequence_barrier.alert();
+        });
+        self.halt_group = halt_group;
+    }
+
+    fn notify_start(&mut self) {
+        let event_handler = &mut self.event_handler;
+        if let Err(error) = panic::catch_unwind(AssertUnwindSafe(|| event_handler.on_start())) {
+            let action = self.exception_handler.handle_on_start_exception(&*error);
+            self.handle_exception(action);
+        }
+    }
+
+    fn notify_shutdown(&mut self) {
+        let event_handler = &mut self.event_handler;
+        if let Err(error) = panic::catch_unwind(AssertUnwindSafe(|| event_handler.on_shutdown())) {
+            self.exception_handler.handle_on_shutdown_exception(&*error);
         }
     }
 
-    fn process_events(&mut self) {
-        let mut next_sequence = self.sequence.get() + 1;
+    fn notify_timeout(&mut self) {
+        let sequence = self.sequence.get();
+        let event_handler = &mut self.event_handler;
+        if let Err(error) =
+            panic::catch_unwind(AssertUnwindSafe(|| event_handler.on_timeout(sequence)))
+        {
+            let action = self
+                .exception_handler
+                .handle_on_timeout_exception(&*error, sequence);
+            self.handle_exception(action);
+        }
+    }
 
-        let available_sequence = self.sequence_barrier.wait_for(next_sequence);
+    async fn process_batch(&mut self, batch_start: i64, available_sequence: i64) -> bool {
+        let mut attempts = 0;
+        loop {
+            match self.handle_batch(batch_start, available_sequence, &mut attempts) {
+                Ok(processed) => return processed,
+                Err(pause) => {
+                    if !pause.is_zero() {
+                        Delay::new(pause).await;
+                    }
+                    if self.state.load(Ordering::Acquire) != STATE_RUNNING {
+                        return false;
+                    }
+                }
+            }
+        }
+    }
 
-        if let Some(sequence) = available_sequence {
-            while next_sequence <= sequence {
-                let event = self.ringbuffer.get_mut(next_sequence as usize);
-                self.event_handler
-                    .on_event(event, next_sequence, next_sequence == sequence);
-                next_sequence += 1;
+    // One pass over the batch. `Err` asks for the batch to be replayed from
+    // its start after the given pause.
+    fn handle_batch(
+        &mut self,
+        batch_start: i64,
+        available_sequence: i64,
+        attempts: &mut u32,
+    ) -> Result<bool, Duration> {
+        // Without a batch size limit the whole backlog is one batch.
+        let batch_size = available_sequence - batch_start + 1;
+        let event_handler = &mut self.event_handler;
+        if let Err(error) = panic::catch_unwind(AssertUnwindSafe(|| {
+            event_handler.on_batch_start(batch_size, batch_size)
+        })) {
+            // Reported against the first event of the batch, which is still
+            // unprocessed if the handler decides to halt.
+            let event = self.ringbuffer.get_mut(batch_start as usize);
+            let action = self
+                .exception_handler
+                .handle_event_exception(&*error, batch_start, event);
+            if action != ExceptionAction::Continue {
+                self.handle_exception(action);
+                return Ok(false);
             }
+        }
+
+        let mut next_sequence = batch_start;
+        while next_sequence <= available_sequence {
+            let event = self.ringbuffer.get_mut(next_sequence as usize);
+            let event_handler = &mut self.event_handler;
+            let end_of_batch = next_sequence == available_sequence;
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                event_handler.on_event_rewindable(event, next_sequence, end_of_batch)
+            }));
 
-            self.sequence.set(sequence);
+            let error: Box<dyn Any + Send> = match result {
+                Ok(Ok(())) => {
+                    #[cfg(feature = "latency")]
+                    self.latency.record(next_sequence);
+                    next_sequence += 1;
+                    continue;
+                }
+                Ok(Err(rewind)) => {
+                    *attempts += 1;
+                    match self.rewind_strategy.handle_rewind(&rewind, *attempts) {
+                        RewindAction::Rewind => return Err(Duration::ZERO),
+                        RewindAction::RewindAfter(pause) => return Err(pause),
+                        // Exception handlers see a plain message, like a panic.
+                        RewindAction::GiveUp => Box::new(rewind.to_string()),
+                    }
+                }
+                Err(error) => error,
+            };
+
+            let action =
+                self.exception_handler
+                    .handle_event_exception(&*error, next_sequence, event);
+            if action != ExceptionAction::Continue {
+                self.sequence.set(next_sequence - 1);
+                self.sequence_barrier.signal_sequence_advanced();
+                self.handle_exception(action);
+                return Ok(false);
+            }
+            next_sequence += 1;
+        }
+
+        Ok(true)
+    }
+
+    fn handle_exception(&mut self, action: ExceptionAction) {
+        match action {
+            ExceptionAction::Continue => {}
+            ExceptionAction::HaltProcessor => self.halt(),
+            ExceptionAction::HaltDisruptor => {
+                self.halt();
+                self.halt_group.halt_all();
+            }
+        }
+    }
+
+    async fn process_events(&mut self) {
+        let next_sequence = self.sequence.get() + 1;
+
+        match self.sequence_barrier.wait_for_async(next_sequence).await {
+            Ok(sequence) => {
+                if next_sequence <= sequence && self.process_batch(next_sequence, sequence).await {
+                    #[cfg(feature = "metrics")]
+                    self.metrics.record_batch(sequence - next_sequence + 1);
+                    self.sequence.set(sequence);
+                    self.sequence_barrier.signal_sequence_advanced();
+                }
+            }
+            Err(WaitError::Timeout) => self.notify_timeout(),
+            Err(WaitError::Alerted) => {}
         }
     }
 }
@@ -89,11 +268,13 @@ where
                 .is_ok()
             {
                 self.sequence_barrier.clear_alert();
+                self.notify_start();
 
                 while self.state.load(Ordering::Acquire) == STATE_RUNNING {
-                    self.process_events();
+                    self.process_events().await;
                 }
 
+                self.notify_shutdown();
                 self.state.store(STATE_IDLE, Ordering::Release);
             }
         })
@@ -118,4 +299,294 @@ where
     fn running(self) -> bool {
         self.state.load(Ordering::Acquire) == STATE_RUNNING
     }
+
+    #[cfg(feature = "metrics")]
+    fn metrics(&self) -> Option<Arc<ProcessorMetrics>> {
+        Some(self.metrics.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Mutex, time::Duration};
+
+    use crate::{
+        DisruptorBuilder, EventHandler, RewindableError,
+        exception_handler::{LogAndSkipExceptionHandler, panic_message},
+        executor::TokioExecutor,
+        rewind_strategy::EventuallyGiveUpBatchRewindStrategy,
+        test_support::{SHUTDOWN_TIMEOUT, TestFactory, test_builder},
+        wait_strategy::TimeoutBlockingWaitStrategy,
+    };
+
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Call {
+        Start,
+        BatchStart(i64),
+        Event(i64),
+        Shutdown,
+    }
+
+    struct LifecycleHandler {
+        calls: Arc<Mutex<Vec<Call>>>,
+    }
+
+    impl EventHandler<i64> for LifecycleHandler {
+        fn on_event(&mut self, _event: &mut i64, sequence: i64, _end_of_batch: bool) {
+            self.calls.lock().unwrap().push(Call::Event(sequence));
+        }
+
+        fn on_start(&mut self) {
+            self.calls.lock().unwrap().push(Call::Start);
+        }
+
+        fn on_shutdown(&mut self) {
+            self.calls.lock().unwrap().push(Call::Shutdown);
+        }
+
+        fn on_batch_start(&mut self, batch_size: i64, _queue_depth: i64) {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(Call::BatchStart(batch_size));
+        }
+    }
+
+    #[test]
+    fn test_lifecycle_hooks_wrap_processing() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let (mut disruptor, mut producer) = test_builder()
+            .handler(LifecycleHandler {
+                calls: calls.clone(),
+            })
+            .build_with_producer();
+
+        disruptor.start();
+        producer.publish_batch(3, |_, e| *e = 1).unwrap();
+        assert!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT).is_ok());
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.first(), Some(&Call::Start));
+        assert_eq!(calls.last(), Some(&Call::Shutdown));
+
+        let batch_sizes: i64 = calls
+            .iter()
+            .filter_map(|c| match c {
+                Call::BatchStart(size) => Some(*size),
+                _ => None,
+            })
+            .sum();
+        assert_eq!(batch_sizes, 3);
+        assert_eq!(
+            calls.iter().filter(|c| matches!(c, Call::Event(_))).count(),
+            3
+        );
+    }
+
+    struct PanickingBatchStartHandler {
+        events: Arc<Mutex<Vec<i64>>>,
+    }
+
+    impl EventHandler<i64> for PanickingBatchStartHandler {
+        fn on_event(&mut self, _event: &mut i64, sequence: i64, _end_of_batch: bool) {
+            self.events.lock().unwrap().push(sequence);
+        }
+
+        fn on_batch_start(&mut self, _batch_size: i64, _queue_depth: i64) {
+            panic!("on_batch_start failed");
+        }
+    }
+
+    #[test]
+    fn test_batch_start_panic_goes_to_exception_handler() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let (mut disruptor, mut producer) = test_builder()
+            .with_exception_handler(LogAndSkipExceptionHandler)
+            .handler(PanickingBatchStartHandler {
+                events: events.clone(),
+            })
+            .build_with_producer();
+
+        disruptor.start();
+        producer.publish_batch(3, |_, e| *e = 1).unwrap();
+        assert!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT).is_ok());
+        assert_eq!(*events.lock().unwrap(), vec![0, 1, 2]);
+    }
+
+    struct PanickingTimeoutHandler {
+        timeouts: Arc<Mutex<usize>>,
+        events: Arc<Mutex<Vec<i64>>>,
+    }
+
+    impl EventHandler<i64> for PanickingTimeoutHandler {
+        fn on_event(&mut self, _event: &mut i64, sequence: i64, _end_of_batch: bool) {
+            self.events.lock().unwrap().push(sequence);
+        }
+
+        fn on_timeout(&mut self, _sequence: i64) {
+            *self.timeouts.lock().unwrap() += 1;
+            panic!("on_timeout failed");
+        }
+    }
+
+    #[test]
+    fn test_timeout_panic_goes_to_exception_handler() {
+        let timeouts = Arc::new(Mutex::new(0));
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let (mut disruptor, mut producer) = DisruptorBuilder::new_single_producer(
+            8,
+            TestFactory,
+            TokioExecutor::new(1),
+            TimeoutBlockingWaitStrategy::new(Duration::from_millis(5)),
+        )
+        .with_exception_handler(LogAndSkipExceptionHandler)
+        .handler(PanickingTimeoutHandler {
+            timeouts: timeouts.clone(),
+            events: events.clone(),
+        })
+        .build_with_producer();
+
+        disruptor.start();
+        while *timeouts.lock().unwrap() < 2 {
+            std::thread::yield_now();
+        }
+        producer.publish(|e| *e = 1);
+        assert!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT).is_ok());
+        assert_eq!(*events.lock().unwrap(), vec![0]);
+    }
+
+    struct RewindingHandler {
+        rewinds: usize,
+        seen: Arc<Mutex<Vec<i64>>>,
+    }
+
+    impl EventHandler<i64> for RewindingHandler {
+        fn on_event_rewindable(
+            &mut self,
+            _event: &mut i64,
+            sequence: i64,
+            _end_of_batch: bool,
+        ) -> Result<(), RewindableError> {
+            self.seen.lock().unwrap().push(sequence);
+            if sequence == 1 && self.rewinds > 0 {
+                self.rewinds -= 1;
+                return Err(RewindableError::new("not yet"));
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_rewind_replays_batch_from_start() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let (mut disruptor, mut producer) = test_builder()
+            .handler(RewindingHandler {
+                rewinds: 2,
+                seen: seen.clone(),
+            })
+            .build_with_producer();
+
+        producer.publish_batch(3, |_, e| *e = 1).unwrap();
+        disruptor.start();
+        assert!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT).is_ok());
+
+        assert_eq!(*seen.lock().unwrap(), vec![0, 1, 0, 1, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_give_up_routes_to_exception_handler() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let (mut disruptor, mut producer) = test_builder()
+            .with_exception_handler(LogAndSkipExceptionHandler)
+            .with_rewind_strategy(EventuallyGiveUpBatchRewindStrategy::new(1))
+            .handler(RewindingHandler {
+                rewinds: usize::MAX,
+                seen: seen.clone(),
+            })
+            .build_with_producer();
+
+        producer.publish_batch(3, |_, e| *e = 1).unwrap();
+        disruptor.start();
+        assert!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT).is_ok());
+
+        assert_eq!(*seen.lock().unwrap(), vec![0, 1, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_rewind_strategy_applies_to_earlier_handlers() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let (mut disruptor, mut producer) = test_builder()
+            .handler(RewindingHandler {
+                rewinds: usize::MAX,
+                seen: seen.clone(),
+            })
+            .with_exception_handler(LogAndSkipExceptionHandler)
+            .with_rewind_strategy(EventuallyGiveUpBatchRewindStrategy::new(1))
+            .build_with_producer();
+
+        producer.publish_batch(3, |_, e| *e = 1).unwrap();
+        disruptor.start();
+        assert!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT).is_ok());
+
+        assert_eq!(*seen.lock().unwrap(), vec![0, 1, 0, 1, 2]);
+    }
+
+    struct RecordingExceptionHandler {
+        messages: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl ExceptionHandler<i64> for RecordingExceptionHandler {
+        fn handle_event_exception(
+            &self,
+            error: &(dyn Any + Send),
+            _sequence: i64,
+            _event: &mut i64,
+        ) -> ExceptionAction {
+            self.messages
+                .lock()
+                .unwrap()
+                .push(panic_message(error).to_string());
+            ExceptionAction::Continue
+        }
+
+        fn handle_on_start_exception(&self, _error: &(dyn Any + Send)) -> ExceptionAction {
+            ExceptionAction::Continue
+        }
+
+        fn handle_on_shutdown_exception(&self, _error: &(dyn Any + Send)) {}
+
+        fn handle_on_timeout_exception(
+            &self,
+            _error: &(dyn Any + Send),
+            _sequence: i64,
+        ) -> ExceptionAction {
+            ExceptionAction::Continue
+        }
+    }
+
+    #[test]
+    fn test_give_up_reports_rewind_message() {
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        let (mut disruptor, mut producer) = test_builder()
+            .with_exception_handler(RecordingExceptionHandler {
+                messages: messages.clone(),
+            })
+            .with_rewind_strategy(EventuallyGiveUpBatchRewindStrategy::new(0))
+            .handler(RewindingHandler {
+                rewinds: 1,
+                seen: Arc::new(Mutex::new(Vec::new())),
+            })
+            .build_with_producer();
+
+        producer.publish_batch(2, |_, e| *e = 1).unwrap();
+        disruptor.start();
+        assert!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT).is_ok());
+
+        assert_eq!(
+            *messages.lock().unwrap(),
+            vec!["batch rewind requested: not yet".to_string()]
+        );
+    }
 }
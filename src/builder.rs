@@ -1,12 +1,19 @@
 use std::sync::Arc;
 
 use crate::{
-    BatchEventProcessor, ConsumerRepository, Disruptor, EventFactory, EventHandler, EventProcessor,
-    EventProcessorAdapter, Executor, MultiProducer, MultiProducerSequencer,
-    ProcessingSequenceBarrier, Producer, RingBuffer, Sequence, Sequencer, SequencerAdapter,
-    SingleProducer, SingleProducerSequencer, WaitStrategy,
+    AsyncBatchEventProcessor, AsyncCapableWaitStrategy, AsyncEventHandler, BatchEventProcessor,
+    BatchRewindStrategy, ConsumerRepository, Disruptor, EventFactory, EventHandler, EventPoller,
+    EventProcessor, EventProcessorAdapter, EventStream, ExceptionHandler, Executor, MultiProducer,
+    MultiProducerSequencer, ProcessingSequenceBarrier, Producer, RingBuffer, Sequence,
+    SequenceBarrier, Sequencer, SequencerAdapter, SingleProducer, SingleProducerSequencer,
+    TryEventHandler, TryEventHandlerAdapter, WaitStrategy, WorkHandler, WorkerPool,
+    exception_handler::{ExceptionHandlerWrapper, HaltGroup},
+    rewind_strategy::RewindStrategyWrapper,
 };
 
+#[cfg(feature = "latency")]
+use crate::latency::LatencyStages;
+
 pub struct DisruptorBuilder<E, Exe, W, S, P>
 where
     E: Send + Sync + 'static,
@@ -23,10 +30,22 @@ where
     sequence_barrier: Arc<ProcessingSequenceBarrier<W, S>>,
     producer: P,
     sequencer: SequencerAdapter<S>,
+    exception_handler: Arc<ExceptionHandlerWrapper<E>>,
+    exception_handlers: Vec<(Arc<Sequence>, Arc<ExceptionHandlerWrapper<E>>)>,
+    rewind_strategy: Arc<RewindStrategyWrapper>,
+    halt_group: HaltGroup,
+    #[cfg(feature = "latency")]
+    latency: LatencyStages,
 }
 
 impl<E, Exe, W>
-    DisruptorBuilder<E, Exe, W, SingleProducerSequencer, SingleProducer<E, SingleProducerSequencer>>
+    DisruptorBuilder<
+        E,
+        Exe,
+        W,
+        SingleProducerSequencer<W>,
+        SingleProducer<E, SingleProducerSequencer<W>>,
+    >
 where
     E: Send + Sync + 'static,
     Exe: Executor + 'static,
@@ -38,13 +57,13 @@ where
         executor: Exe,
         wait_strategy: W,
     ) -> Self {
-        let sequencer = SingleProducerSequencer::new(buffer_size);
+        let wait_strategy_arc = Arc::new(wait_strategy);
+        let sequencer = SingleProducerSequencer::new(buffer_size, wait_strategy_arc.clone());
         let sequencer_adapter = SequencerAdapter::new(sequencer);
         let ringbuffer = Arc::new(RingBuffer::new(buffer_size as usize, event_factory));
         let producer = SingleProducer::new(sequencer_adapter.clone(), ringbuffer.clone());
 
         let cursor = sequencer_adapter.cursor();
-        let wait_strategy_arc = Arc::new(wait_strategy);
         let sequence_barrier = Arc::new(ProcessingSequenceBarrier::new(
             wait_strategy_arc.clone(),
             sequencer_adapter.clone(),
@@ -60,29 +79,42 @@ where
             sequence_barrier,
             producer,
             sequencer: sequencer_adapter,
+            exception_handler: Arc::new(ExceptionHandlerWrapper::new()),
+            exception_handlers: Vec::new(),
+            rewind_strategy: Arc::new(RewindStrategyWrapper::new()),
+            halt_group: HaltGroup::new(),
+            #[cfg(feature = "latency")]
+            latency: LatencyStages::default(),
         }
     }
 
+    #[allow(clippy::type_complexity)]
     pub fn build_with_producer(
         self,
     ) -> (
-        Disruptor<E, Exe, SingleProducerSequencer>,
-        SingleProducer<E, SingleProducerSequencer>,
+        Disruptor<E, Exe, SingleProducerSequencer<W>>,
+        SingleProducer<E, SingleProducerSequencer<W>>,
     ) {
-        (
-            Disruptor::new(
-                self.executor,
-                self.ringbuffer.clone(),
-                self.repository,
-                self.sequencer,
-            ),
-            self.producer,
-        )
+        let disruptor = Disruptor::new(
+            self.executor,
+            self.ringbuffer.clone(),
+            self.repository,
+            self.sequencer,
+        );
+        #[cfg(feature = "latency")]
+        let disruptor = disruptor.with_latency_tracer(self.latency.tracer());
+        (disruptor, self.producer)
     }
 }
 
 impl<E, Exe, W>
-    DisruptorBuilder<E, Exe, W, MultiProducerSequencer, MultiProducer<E, MultiProducerSequencer>>
+    DisruptorBuilder<
+        E,
+        Exe,
+        W,
+        MultiProducerSequencer<W>,
+        MultiProducer<E, MultiProducerSequencer<W>>,
+    >
 where
     E: Send + Sync + 'static,
     Exe: Executor + 'static,
@@ -93,15 +125,20 @@ where
         event_factory: F,
         executor: Exe,
         wait_strategy: W,
-    ) -> DisruptorBuilder<E, Exe, W, MultiProducerSequencer, MultiProducer<E, MultiProducerSequencer>>
-    {
-        let sequencer = MultiProducerSequencer::new(buffer_size);
+    ) -> DisruptorBuilder<
+        E,
+        Exe,
+        W,
+        MultiProducerSequencer<W>,
+        MultiProducer<E, MultiProducerSequencer<W>>,
+    > {
+        let wait_strategy_arc = Arc::new(wait_strategy);
+        let sequencer = MultiProducerSequencer::new(buffer_size, wait_strategy_arc.clone());
         let sequencer_adapter = SequencerAdapter::new(sequencer);
         let ringbuffer = Arc::new(RingBuffer::new(buffer_size as usize, event_factory));
         let producer = MultiProducer::new(sequencer_adapter.clone(), ringbuffer.clone());
 
         let cursor = sequencer_adapter.cursor();
-        let wait_strategy_arc = Arc::new(wait_strategy);
         let sequence_barrier = Arc::new(ProcessingSequenceBarrier::new(
             wait_strategy_arc.clone(),
             sequencer_adapter.clone(),
@@ -117,27 +154,34 @@ where
             sequence_barrier,
             producer,
             sequencer: sequencer_adapter,
+            exception_handler: Arc::new(ExceptionHandlerWrapper::new()),
+            exception_handlers: Vec::new(),
+            rewind_strategy: Arc::new(RewindStrategyWrapper::new()),
+            halt_group: HaltGroup::new(),
+            #[cfg(feature = "latency")]
+            latency: LatencyStages::default(),
         }
     }
 
+    #[allow(clippy::type_complexity)]
     pub fn build_with_producer(
         self,
     ) -> (
-        Disruptor<E, Exe, MultiProducerSequencer>,
-        MultiProducer<E, MultiProducerSequencer>,
+        Disruptor<E, Exe, MultiProducerSequencer<W>>,
+        MultiProducer<E, MultiProducerSequencer<W>>,
     ) {
-        (
-            Disruptor::new(
-                self.executor,
-                self.ringbuffer,
-                self.repository,
-                self.sequencer,
-            ),
-            self.producer,
-        )
+        let disruptor = Disruptor::new(
+            self.executor,
+            self.ringbuffer,
+            self.repository,
+            self.sequencer,
+        );
+        #[cfg(feature = "latency")]
+        let disruptor = disruptor.with_latency_tracer(self.latency.tracer());
+        (disruptor, self.producer)
     }
 
-    pub fn producer(&self) -> MultiProducer<E, MultiProducerSequencer> {
+    pub fn producer(&self) -> MultiProducer<E, MultiProducerSequencer<W>> {
         self.producer.clone()
     }
 }
@@ -147,7 +191,13 @@ pub fn builder<E, F, Exe, W>(
     event_factory: F,
     executor: Exe,
     wait_strategy: W,
-) -> DisruptorBuilder<E, Exe, W, SingleProducerSequencer, SingleProducer<E, SingleProducerSequencer>>
+) -> DisruptorBuilder<
+    E,
+    Exe,
+    W,
+    SingleProducerSequencer<W>,
+    SingleProducer<E, SingleProducerSequencer<W>>,
+>
 where
     E: Send + Sync + 'static,
     F: EventFactory<E> + 'static,
@@ -166,37 +216,61 @@ where
     P: Producer<E, S>,
 {
     pub fn handler<H: EventHandler<E> + 'static>(mut self, handler: H) -> Self {
-        let sequence_barrier = Arc::new(ProcessingSequenceBarrier::new(
-            self.wait_strategy.clone(),
-            self.sequencer.clone(),
-            vec![self.sequencer.cursor()],
-        ));
-
-        let processor = BatchEventProcessor::new(
-            handler,
-            Arc::clone(&self.ringbuffer),
-            Arc::clone(&sequence_barrier),
-        );
+        self.handle(handler);
+        self
+    }
 
-        let sequence = processor.sequence();
+    /// Sets the default exception handler for every processor this builder
+    /// creates, including those registered before the call;
+    /// `handle_exceptions_for` overrides it for a single handler. Panics in `on_event`,
+    /// `on_start`, `on_shutdown`, `on_batch_start` and `on_timeout`, and
+    /// batches a rewind strategy gives up on, all end up here. Defaults to
+    /// `HaltProcessorExceptionHandler`.
+    pub fn with_exception_handler<X: ExceptionHandler<E>>(self, exception_handler: X) -> Self {
+        self.exception_handler
+            .switch_to(Arc::new(exception_handler));
+        self
+    }
 
-        self.sequences.clear();
+    // Overrides the builder-wide exception handler for one handler. Panics if
+    // the handle came from a different builder.
+    pub fn handle_exceptions_for(&self, handle: &HandlerHandle) -> ExceptionHandlerSetting<E> {
+        let exception_handler = self
+            .exception_handlers
+            .iter()
+            .find(|(sequence, _)| Arc::ptr_eq(sequence, &handle.sequence()))
+            .map(|(_, exception_handler)| exception_handler.clone())
+            .expect("handle was not registered with this builder");
+        ExceptionHandlerSetting { exception_handler }
+    }
 
-        self.sequences.push(Arc::clone(&sequence));
-        self.repository
-            .add(EventProcessorAdapter::new(Box::new(processor)));
-        self.sequencer.add_gating_sequence(sequence);
-        self.sequence_barrier = sequence_barrier;
+    // Applies to every handler, whether registered before or after this call.
+    pub fn with_rewind_strategy<R: BatchRewindStrategy>(self, rewind_strategy: R) -> Self {
+        self.rewind_strategy.switch_to(Arc::new(rewind_strategy));
+        self
+    }
 
+    // Traces every handler, whether registered before or after this call.
+    #[cfg(feature = "latency")]
+    pub fn with_latency_tracing(mut self) -> Self {
+        let latency = self.latency.enable(self.sequencer.buffer_size() as usize);
+        self.producer
+            .mut_publisher()
+            .set_publish_timestamps(latency.timestamps());
         self
     }
 
     pub fn and<H: EventHandler<E> + 'static>(mut self, handler: H) -> Self {
-        let processor = BatchEventProcessor::new(
+        let mut processor = BatchEventProcessor::new(
             handler,
             Arc::clone(&self.ringbuffer),
             Arc::clone(&self.sequence_barrier),
         );
+        processor.set_exception_handler(self.processor_exception_handler(&[processor.sequence()]));
+        processor.set_rewind_strategy(self.rewind_strategy.clone());
+        processor.join_halt_group(self.halt_group.clone());
+        #[cfg(feature = "latency")]
+        processor.trace_latency(self.latency.stage(None));
 
         let sequence = processor.sequence();
         self.sequences.push(Arc::clone(&sequence));
@@ -208,31 +282,404 @@ where
     }
 
     pub fn then<H: EventHandler<E> + 'static>(mut self, handler: H) -> Self {
-        let wait_strategy = self.wait_strategy.clone();
+        let dependencies = self.sequences.clone();
+        self.register(handler, dependencies, None);
+        self
+    }
 
-        let sequence_barrier = Arc::new(ProcessingSequenceBarrier::new(
-            wait_strategy,
-            self.sequencer.clone(),
-            self.sequences.clone(),
-        ));
+    pub fn handler_named<H: EventHandler<E> + 'static>(mut self, name: &str, handler: H) -> Self {
+        self.register(handler, Vec::new(), Some(name));
+        self
+    }
+
+    pub fn then_named<H: EventHandler<E> + 'static>(mut self, name: &str, handler: H) -> Self {
+        let dependencies = self.sequences.clone();
+        self.register(handler, dependencies, Some(name));
+        self
+    }
+
+    pub fn handler_async<H: AsyncEventHandler<E>>(mut self, handler: H) -> Self {
+        self.register_async(handler, Vec::new());
+        self
+    }
+
+    pub fn then_async<H: AsyncEventHandler<E>>(mut self, handler: H) -> Self {
+        let dependencies = self.sequences.clone();
+        self.register_async(handler, dependencies);
+        self
+    }
+
+    pub fn handler_try<T, A>(mut self, handler: A) -> Self
+    where
+        T: TryEventHandler<E>,
+        A: Into<TryEventHandlerAdapter<E, T>>,
+    {
+        self.register_async(handler.into(), Vec::new());
+        self
+    }
+
+    pub fn then_try<T, A>(mut self, handler: A) -> Self
+    where
+        T: TryEventHandler<E>,
+        A: Into<TryEventHandlerAdapter<E, T>>,
+    {
+        let dependencies = self.sequences.clone();
+        self.register_async(handler.into(), dependencies);
+        self
+    }
+
+    pub fn handle<H: EventHandler<E> + 'static>(&mut self, handler: H) -> HandlerHandle {
+        self.register(handler, Vec::new(), None)
+    }
+
+    pub fn handle_named<H: EventHandler<E> + 'static>(
+        &mut self,
+        name: &str,
+        handler: H,
+    ) -> HandlerHandle {
+        self.register(handler, Vec::new(), Some(name))
+    }
+
+    pub fn handle_async<H: AsyncEventHandler<E>>(&mut self, handler: H) -> HandlerHandle {
+        self.register_async(handler, Vec::new())
+    }
+
+    pub fn handle_try<T, A>(&mut self, handler: A) -> HandlerHandle
+    where
+        T: TryEventHandler<E>,
+        A: Into<TryEventHandlerAdapter<E, T>>,
+    {
+        self.register_async(handler.into(), Vec::new())
+    }
 
-        let processor = BatchEventProcessor::new(
+    pub fn handle_worker_pool<H, I>(&mut self, handlers: I) -> HandlerHandle
+    where
+        H: WorkHandler<E> + 'static,
+        I: IntoIterator<Item = H>,
+    {
+        self.add_worker_pool(handlers, Vec::new())
+    }
+
+    pub fn after(&mut self, handles: &[HandlerHandle]) -> DependentBuilder<'_, E, Exe, W, S, P> {
+        DependentBuilder {
+            dependencies: handles
+                .iter()
+                .flat_map(|h| h.sequences.iter().cloned())
+                .collect(),
+            builder: self,
+        }
+    }
+
+    pub fn poller(&mut self) -> EventPoller<E, S> {
+        self.add_poller(Vec::new())
+    }
+
+    pub fn stream(&mut self) -> EventStream<E, ProcessingSequenceBarrier<W, S>>
+    where
+        W: AsyncCapableWaitStrategy,
+    {
+        self.add_stream(Vec::new())
+    }
+
+    pub fn handler_with_worker_pool<H, I>(mut self, handlers: I) -> Self
+    where
+        H: WorkHandler<E> + 'static,
+        I: IntoIterator<Item = H>,
+    {
+        self.add_worker_pool(handlers, Vec::new());
+        self
+    }
+
+    pub fn then_worker_pool<H, I>(mut self, handlers: I) -> Self
+    where
+        H: WorkHandler<E> + 'static,
+        I: IntoIterator<Item = H>,
+    {
+        let dependencies = self.sequences.clone();
+        self.add_worker_pool(handlers, dependencies);
+        self
+    }
+
+    fn register<H: EventHandler<E> + 'static>(
+        &mut self,
+        handler: H,
+        dependencies: Vec<Arc<Sequence>>,
+        name: Option<&str>,
+    ) -> HandlerHandle {
+        let name = name.map(|name| (name, self.name_index(name)));
+        let sequence_barrier = self.dependent_barrier(dependencies);
+
+        let mut processor = BatchEventProcessor::new(
             handler,
             Arc::clone(&self.ringbuffer),
             Arc::clone(&sequence_barrier),
         );
+        processor.set_exception_handler(self.processor_exception_handler(&[processor.sequence()]));
+        processor.set_rewind_strategy(self.rewind_strategy.clone());
+        processor.join_halt_group(self.halt_group.clone());
+        #[cfg(feature = "latency")]
+        processor.trace_latency(self.latency.stage(name));
+
+        let sequence = processor.sequence();
+        self.sequences.clear();
+        self.sequences.push(Arc::clone(&sequence));
+        let adapter = match name {
+            Some((name, index)) => EventProcessorAdapter::named(Box::new(processor), name, index),
+            None => EventProcessorAdapter::new(Box::new(processor)),
+        };
+        self.repository.add(adapter);
+        self.sequencer.add_gating_sequence(Arc::clone(&sequence));
+        self.sequence_barrier = sequence_barrier;
+
+        HandlerHandle {
+            sequences: vec![sequence],
+        }
+    }
+
+    // Processors sharing a name are numbered in registration order.
+    fn name_index(&self, name: &str) -> usize {
+        self.repository
+            .get_processors()
+            .iter()
+            .filter(|p| p.name().is_some_and(|(n, _)| n == name))
+            .count()
+    }
+
+    fn register_async<H: AsyncEventHandler<E>>(
+        &mut self,
+        handler: H,
+        dependencies: Vec<Arc<Sequence>>,
+    ) -> HandlerHandle {
+        let sequence_barrier = self.dependent_barrier(dependencies);
+
+        let mut processor = AsyncBatchEventProcessor::new(
+            handler,
+            Arc::clone(&self.ringbuffer),
+            Arc::clone(&sequence_barrier),
+        );
+        processor.set_exception_handler(self.processor_exception_handler(&[processor.sequence()]));
+        processor.join_halt_group(self.halt_group.clone());
+        #[cfg(feature = "latency")]
+        processor.trace_latency(self.latency.stage(None));
 
         let sequence = processor.sequence();
         self.sequences.clear();
         self.sequences.push(Arc::clone(&sequence));
         self.repository
             .add(EventProcessorAdapter::new(Box::new(processor)));
-        self.sequencer.add_gating_sequence(sequence);
+        self.sequencer.add_gating_sequence(Arc::clone(&sequence));
+        self.sequence_barrier = sequence_barrier;
 
-        Self {
-            sequence_barrier,
-            ..self
+        HandlerHandle {
+            sequences: vec![sequence],
+        }
+    }
+
+    fn add_poller(&mut self, dependencies: Vec<Arc<Sequence>>) -> EventPoller<E, S> {
+        for dependency in dependencies.iter() {
+            self.sequencer.remove_gating_sequence(dependency);
+        }
+
+        let sequence = Arc::new(Sequence::new());
+        self.sequencer.add_gating_sequence(Arc::clone(&sequence));
+
+        EventPoller::new(
+            Arc::clone(&self.ringbuffer),
+            self.sequencer.clone(),
+            sequence,
+            dependencies,
+        )
+    }
+
+    fn add_stream(
+        &mut self,
+        dependencies: Vec<Arc<Sequence>>,
+    ) -> EventStream<E, ProcessingSequenceBarrier<W, S>>
+    where
+        W: AsyncCapableWaitStrategy,
+    {
+        let sequence_barrier = self.dependent_barrier(dependencies);
+
+        let alert_barrier = Arc::clone(&sequence_barrier);
+        self.halt_group.add(move || alert_barrier.alert());
+
+        let sequence = Arc::new(Sequence::new());
+        self.sequencer.add_gating_sequence(Arc::clone(&sequence));
+
+        let alert_barrier = Arc::clone(&sequence_barrier);
+        self.repository
+            .add_stream(Arc::clone(&sequence), move || alert_barrier.alert());
+
+        EventStream::new(Arc::clone(&self.ringbuffer), sequence_barrier, sequence)
+    }
+
+    fn add_worker_pool<H, I>(
+        &mut self,
+        handlers: I,
+        dependencies: Vec<Arc<Sequence>>,
+    ) -> HandlerHandle
+    where
+        H: WorkHandler<E> + 'static,
+        I: IntoIterator<Item = H>,
+    {
+        let sequence_barrier = self.dependent_barrier(dependencies);
+
+        let pool = WorkerPool::new(
+            handlers,
+            Arc::clone(&self.ringbuffer),
+            Arc::clone(&sequence_barrier),
+        );
+
+        // Workers in a pool share one stage histogram.
+        #[cfg(feature = "latency")]
+        let stage = self.latency.stage(None);
+
+        // Workers in a pool share one exception handler as well.
+        let processors = pool.into_processors();
+        let sequences: Vec<_> = processors.iter().map(|p| p.sequence()).collect();
+        let exception_handler = self.processor_exception_handler(&sequences);
+
+        self.sequences.clear();
+        for mut processor in processors {
+            processor.set_exception_handler(exception_handler.clone());
+            processor.join_halt_group(&self.halt_group);
+            #[cfg(feature = "latency")]
+            processor.trace_latency(stage.clone());
+            let sequence = processor.sequence();
+            self.sequences.push(Arc::clone(&sequence));
+            self.repository
+                .add(EventProcessorAdapter::new(Box::new(processor)));
+            self.sequencer.add_gating_sequence(sequence);
         }
+        self.sequence_barrier = sequence_barrier;
+
+        HandlerHandle { sequences }
+    }
+
+    fn processor_exception_handler(
+        &mut self,
+        sequences: &[Arc<Sequence>],
+    ) -> Arc<ExceptionHandlerWrapper<E>> {
+        let exception_handler = Arc::new(ExceptionHandlerWrapper::delegating_to(
+            self.exception_handler.clone(),
+        ));
+        for sequence in sequences {
+            self.exception_handlers
+                .push((Arc::clone(sequence), exception_handler.clone()));
+        }
+        exception_handler
+    }
+
+    // Only leaf processors gate the producer, so the sequences a new stage
+    // depends on stop gating once it is registered.
+    fn dependent_barrier(
+        &mut self,
+        dependencies: Vec<Arc<Sequence>>,
+    ) -> Arc<ProcessingSequenceBarrier<W, S>> {
+        for dependency in dependencies.iter() {
+            self.sequencer.remove_gating_sequence(dependency);
+        }
+
+        Arc::new(ProcessingSequenceBarrier::new(
+            self.wait_strategy.clone(),
+            self.sequencer.clone(),
+            dependencies,
+        ))
+    }
+}
+
+// A worker pool's handle holds one sequence per worker; stages after it wait
+// for all of them.
+#[derive(Clone)]
+pub struct HandlerHandle {
+    sequences: Vec<Arc<Sequence>>,
+}
+
+impl HandlerHandle {
+    // For a worker pool, the first worker's sequence.
+    pub fn sequence(&self) -> Arc<Sequence> {
+        self.sequences[0].clone()
+    }
+
+    pub fn sequences(&self) -> &[Arc<Sequence>] {
+        &self.sequences
+    }
+}
+
+pub struct ExceptionHandlerSetting<E> {
+    exception_handler: Arc<ExceptionHandlerWrapper<E>>,
+}
+
+impl<E: 'static> ExceptionHandlerSetting<E> {
+    pub fn with<X: ExceptionHandler<E>>(self, exception_handler: X) {
+        self.exception_handler
+            .switch_to(Arc::new(exception_handler));
+    }
+}
+
+pub struct DependentBuilder<'a, E, Exe, W, S, P>
+where
+    E: Send + Sync + 'static,
+    Exe: Executor + 'static,
+    W: WaitStrategy + 'static,
+    S: Sequencer,
+    P: Producer<E, S>,
+{
+    builder: &'a mut DisruptorBuilder<E, Exe, W, S, P>,
+    dependencies: Vec<Arc<Sequence>>,
+}
+
+impl<E, Exe, W, S, P> DependentBuilder<'_, E, Exe, W, S, P>
+where
+    E: Send + Sync + 'static,
+    Exe: Executor + 'static,
+    W: WaitStrategy + 'static,
+    S: Sequencer,
+    P: Producer<E, S>,
+{
+    pub fn handle<H: EventHandler<E> + 'static>(self, handler: H) -> HandlerHandle {
+        self.builder.register(handler, self.dependencies, None)
+    }
+
+    pub fn handle_named<H: EventHandler<E> + 'static>(
+        self,
+        name: &str,
+        handler: H,
+    ) -> HandlerHandle {
+        self.builder
+            .register(handler, self.dependencies, Some(name))
+    }
+
+    pub fn handle_async<H: AsyncEventHandler<E>>(self, handler: H) -> HandlerHandle {
+        self.builder.register_async(handler, self.dependencies)
+    }
+
+    pub fn handle_try<T, A>(self, handler: A) -> HandlerHandle
+    where
+        T: TryEventHandler<E>,
+        A: Into<TryEventHandlerAdapter<E, T>>,
+    {
+        self.builder
+            .register_async(handler.into(), self.dependencies)
+    }
+
+    pub fn handle_worker_pool<H, I>(self, handlers: I) -> HandlerHandle
+    where
+        H: WorkHandler<E> + 'static,
+        I: IntoIterator<Item = H>,
+    {
+        self.builder.add_worker_pool(handlers, self.dependencies)
+    }
+
+    pub fn poller(self) -> EventPoller<E, S> {
+        self.builder.add_poller(self.dependencies)
+    }
+
+    pub fn stream(self) -> EventStream<E, ProcessingSequenceBarrier<W, S>>
+    where
+        W: AsyncCapableWaitStrategy,
+    {
+        self.builder.add_stream(self.dependencies)
     }
 }
 
@@ -252,8 +699,13 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
     use crate::{
-        EventFactory, EventHandler, executor::TokioExecutor, wait_strategy::BusySpinWaitStrategy,
+        EventFactory, EventHandler,
+        executor::TokioExecutor,
+        test_support::SHUTDOWN_TIMEOUT,
+        wait_strategy::{AsyncWaitStrategy, BlockingWaitStrategy, BusySpinWaitStrategy},
     };
 
     struct TestEvent {
@@ -396,4 +848,211 @@ mod tests {
         assert!(!disruptor.has_backlog());
         let _ = producer;
     }
+
+    struct CountingWorkHandler {
+        count: Arc<AtomicUsize>,
+    }
+
+    impl WorkHandler<TestEvent> for CountingWorkHandler {
+        fn on_event(&mut self, _event: &mut TestEvent) {
+            self.count.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_worker_pool_processes_each_event_once() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let (mut disruptor, mut producer) = builder(
+            8,
+            TestFactory,
+            TokioExecutor::new(4),
+            BlockingWaitStrategy::new(),
+        )
+        .handler(TestEventHandler {
+            name: "handler1".to_string(),
+        })
+        .then_worker_pool((0..3).map(|_| CountingWorkHandler {
+            count: count.clone(),
+        }))
+        .build_with_producer();
+
+        disruptor.start();
+        for i in 0..100 {
+            producer.publish(|event| event.value = i.to_string());
+        }
+
+        assert!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT).is_ok());
+        assert_eq!(count.load(Ordering::Relaxed), 100);
+    }
+
+    struct DependentHandler {
+        dependencies: Vec<HandlerHandle>,
+        violations: Arc<AtomicUsize>,
+        count: Arc<AtomicUsize>,
+    }
+
+    impl EventHandler<TestEvent> for DependentHandler {
+        fn on_event(&mut self, _event: &mut TestEvent, sequence: i64, _end_of_batch: bool) {
+            if self
+                .dependencies
+                .iter()
+                .flat_map(|h| h.sequences())
+                .any(|s| s.get() < sequence)
+            {
+                self.violations.fetch_add(1, Ordering::Relaxed);
+            }
+            self.count.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_after_builds_diamond() {
+        let violations = Arc::new(AtomicUsize::new(0));
+        let count = Arc::new(AtomicUsize::new(0));
+        let mut builder = builder(
+            8,
+            TestFactory,
+            TokioExecutor::new(4),
+            BlockingWaitStrategy::new(),
+        );
+
+        let h_a = builder.handle(TestEventHandler {
+            name: "a".to_string(),
+        });
+        let h_b = builder.handle(TestEventHandler {
+            name: "b".to_string(),
+        });
+        let c_dependencies = vec![h_a.clone(), h_b.clone()];
+        builder.after(&c_dependencies).handle(DependentHandler {
+            dependencies: c_dependencies.clone(),
+            violations: violations.clone(),
+            count: count.clone(),
+        });
+        let d_dependencies = vec![h_a.clone()];
+        builder.after(&d_dependencies).handle(DependentHandler {
+            dependencies: d_dependencies.clone(),
+            violations: violations.clone(),
+            count: count.clone(),
+        });
+
+        assert!(!builder.sequencer.remove_gating_sequence(&h_a.sequence()));
+        assert!(!builder.sequencer.remove_gating_sequence(&h_b.sequence()));
+
+        let (mut disruptor, mut producer) = builder.build_with_producer();
+        disruptor.start();
+        for i in 0..50 {
+            producer.publish(|event| event.value = i.to_string());
+        }
+
+        assert!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT).is_ok());
+        assert_eq!(count.load(Ordering::Relaxed), 100);
+        assert_eq!(violations.load(Ordering::Relaxed), 0);
+    }
+
+    struct AsyncDependentHandler {
+        inner: DependentHandler,
+    }
+
+    impl AsyncEventHandler<TestEvent> for AsyncDependentHandler {
+        async fn on_event(&mut self, event: &mut TestEvent, sequence: i64, end_of_batch: bool) {
+            self.inner.on_event(event, sequence, end_of_batch);
+        }
+    }
+
+    #[test]
+    fn test_after_builds_diamond_from_named_async_and_pool_stages() {
+        let violations = Arc::new(AtomicUsize::new(0));
+        let count = Arc::new(AtomicUsize::new(0));
+        let pool_count = Arc::new(AtomicUsize::new(0));
+        let mut builder = builder(
+            8,
+            TestFactory,
+            TokioExecutor::new(4),
+            AsyncWaitStrategy::new(),
+        );
+
+        let h_a = builder.handle_named(
+            "a",
+            TestEventHandler {
+                name: "a".to_string(),
+            },
+        );
+        let h_b = builder.handle_async(AsyncDependentHandler {
+            inner: DependentHandler {
+                dependencies: Vec::new(),
+                violations: violations.clone(),
+                count: count.clone(),
+            },
+        });
+        let h_pool = builder.handle_worker_pool((0..2).map(|_| CountingWorkHandler {
+            count: pool_count.clone(),
+        }));
+        let c_dependencies = vec![h_a.clone(), h_b.clone(), h_pool.clone()];
+        builder.after(&c_dependencies).handle_named(
+            "c",
+            DependentHandler {
+                dependencies: c_dependencies.clone(),
+                violations: violations.clone(),
+                count: count.clone(),
+            },
+        );
+        let d_dependencies = vec![h_pool.clone()];
+        builder
+            .after(&d_dependencies)
+            .handle_async(AsyncDependentHandler {
+                inner: DependentHandler {
+                    dependencies: d_dependencies.clone(),
+                    violations: violations.clone(),
+                    count: count.clone(),
+                },
+            });
+
+        for sequence in h_pool.sequences() {
+            assert!(!builder.sequencer.remove_gating_sequence(sequence));
+        }
+
+        let (mut disruptor, mut producer) = builder.build_with_producer();
+        disruptor.start();
+        for i in 0..50 {
+            producer.publish(|event| event.value = i.to_string());
+        }
+
+        assert!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT).is_ok());
+        assert_eq!(count.load(Ordering::Relaxed), 150);
+        assert_eq!(pool_count.load(Ordering::Relaxed), 50);
+        assert_eq!(violations.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_poller_after_handler() {
+        let mut builder = builder(
+            8,
+            TestFactory,
+            TokioExecutor::new(1),
+            BlockingWaitStrategy::new(),
+        );
+        let handle = builder.handle(TestEventHandler {
+            name: "handler1".to_string(),
+        });
+        let dependencies = vec![handle.clone()];
+        let mut poller = builder.after(&dependencies).poller();
+        assert!(!builder.sequencer.remove_gating_sequence(&handle.sequence()));
+
+        let (mut disruptor, mut producer) = builder.build_with_producer();
+        disruptor.start();
+        for i in 0..3 {
+            producer.publish(|event| event.value = i.to_string());
+        }
+
+        let mut values = Vec::new();
+        while values.len() < 3 {
+            poller.poll(|event, sequence, _| {
+                assert!(handle.sequence().get() >= sequence);
+                values.push(event.value.clone());
+                true
+            });
+        }
+        assert_eq!(values, vec!["0", "1", "2"]);
+        disruptor.stop();
+    }
 }
@@ -1,18 +1,48 @@
-use crate::EventProcessorAdapter;
+use std::sync::Arc;
+
+use crate::{EventProcessorAdapter, Sequence};
+
+// A stream is driven by whoever polls it rather than by the executor, so the
+// repository only keeps what the disruptor needs to end it and to check its
+// backlog.
+pub(crate) struct StreamConsumer {
+    pub(crate) sequence: Arc<Sequence>,
+    pub(crate) alert: Box<dyn Fn() + Send + Sync>,
+}
 
 pub struct ConsumerRepository {
     processors: Vec<EventProcessorAdapter>,
+    streams: Vec<StreamConsumer>,
+}
+
+impl Default for ConsumerRepository {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ConsumerRepository {
     pub fn new() -> ConsumerRepository {
-        ConsumerRepository { processors: vec![] }
+        ConsumerRepository {
+            processors: vec![],
+            streams: vec![],
+        }
     }
 
     pub fn add(&mut self, processor: EventProcessorAdapter) {
         self.processors.push(processor);
     }
 
+    pub(crate) fn add_stream<F>(&mut self, sequence: Arc<Sequence>, alert: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.streams.push(StreamConsumer {
+            sequence,
+            alert: Box::new(alert),
+        });
+    }
+
     pub fn size(&self) -> usize {
         self.processors.len()
     }
@@ -24,4 +54,8 @@ impl ConsumerRepository {
     pub fn get_mut_processors(&mut self) -> &mut Vec<EventProcessorAdapter> {
         &mut self.processors
     }
+
+    pub(crate) fn get_streams(&self) -> &Vec<StreamConsumer> {
+        &self.streams
+    }
 }
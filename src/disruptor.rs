@@ -1,6 +1,21 @@
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
 
-use crate::{ConsumerRepository, Executor, RingBuffer, Runnable, Sequencer, SequencerAdapter};
+use crate::{
+    ConsumerRepository, EventPoller, Executor, JoinHandle, RingBuffer, Runnable, Sequence,
+    Sequencer, SequencerAdapter, ShutdownTimeout, prometheus::DisruptorExporter, util,
+};
+
+#[cfg(feature = "latency")]
+use crate::latency::{LatencyTracer, StageLatency};
+#[cfg(feature = "metrics")]
+use crate::metrics::{DisruptorMetrics, ProcessorSnapshot};
+
+pub(crate) const DRAIN_MIN_BACKOFF: Duration = Duration::from_micros(50);
+pub(crate) const DRAIN_MAX_BACKOFF: Duration = Duration::from_millis(10);
 
 pub struct Disruptor<E, Exe, S>
 where
@@ -12,6 +27,9 @@ where
     ringbuffer: Arc<RingBuffer<E>>,
     repository: ConsumerRepository,
     sequencer: SequencerAdapter<S>,
+    handles: Vec<Box<dyn JoinHandle>>,
+    #[cfg(feature = "latency")]
+    latency: Option<Arc<LatencyTracer>>,
 }
 
 impl<E, Exe, S> Disruptor<E, Exe, S>
@@ -31,6 +49,9 @@ where
             ringbuffer,
             repository,
             sequencer,
+            handles: Vec::new(),
+            #[cfg(feature = "latency")]
+            latency: None,
         }
     }
 }
@@ -43,16 +64,150 @@ where
 {
     pub fn start(&mut self) {
         for processor in self.repository.get_processors() {
-            let processor = processor.clone();
-            let r: Box<dyn Runnable> = Box::new(processor);
-            self.executor.execute(r);
+            let name = processor
+                .name()
+                .map(|(name, index)| (name.to_string(), index));
+            let r: Box<dyn Runnable> = Box::new(processor.clone());
+            let handle = match name {
+                Some((name, index)) => self.executor.execute_named(r, &name, index),
+                None => self.executor.execute(r),
+            };
+            self.handles.push(handle);
         }
     }
 
     pub fn stop(&mut self) {
+        self.halt();
+        self.join();
+    }
+
+    pub fn halt(&mut self) {
         for processor in self.repository.get_mut_processors() {
             processor.halt();
         }
+        for stream in self.repository.get_streams() {
+            (stream.alert)();
+        }
+    }
+
+    pub fn join(&mut self) {
+        for handle in self.handles.drain(..) {
+            handle.join();
+        }
+    }
+
+    pub fn shutdown(&mut self) {
+        let _ = self.drain(None);
+        self.stop();
+    }
+
+    // On timeout the processors are left running so the caller can retry or
+    // fall back to `stop`.
+    pub fn shutdown_timeout(&mut self, timeout: Duration) -> Result<(), ShutdownTimeout> {
+        self.drain(Some(Instant::now() + timeout))?;
+        self.stop();
+        Ok(())
+    }
+
+    // Sleeps between checks, doubling up to `DRAIN_MAX_BACKOFF`, so a long
+    // drain doesn't keep a core busy.
+    fn drain(&mut self, deadline: Option<Instant>) -> Result<(), ShutdownTimeout> {
+        let mut backoff = DRAIN_MIN_BACKOFF;
+        while self.has_backlog() {
+            let mut pause = backoff;
+            if let Some(deadline) = deadline {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Err(ShutdownTimeout);
+                }
+                pause = pause.min(remaining);
+            }
+            thread::sleep(pause);
+            backoff = (backoff * 2).min(DRAIN_MAX_BACKOFF);
+        }
+        Ok(())
+    }
+
+    pub fn poller(&self) -> EventPoller<E, S> {
+        let cursor = self.sequencer.cursor();
+        let sequence = Arc::new(Sequence::new());
+        // Seed before and after joining the gating list: the first value
+        // holds the producer back once it sees the new list, the second
+        // catches up with anything published while joining.
+        sequence.set(self.highest_published());
+        self.sequencer.add_gating_sequence(sequence.clone());
+        sequence.set(self.highest_published());
+
+        EventPoller::new(
+            self.ringbuffer.clone(),
+            self.sequencer.clone(),
+            sequence,
+            vec![cursor],
+        )
+    }
+
+    // A multi-producer cursor can run ahead of slots that are claimed but not
+    // yet published, so start from the last contiguously published one.
+    fn highest_published(&self) -> i64 {
+        let cursor = self.sequencer.cursor().get();
+        let consumed = util::minimum_sequence_with(&self.sequencer.gating_sequences(), cursor);
+        let lowest = consumed.max(cursor - self.sequencer.buffer_size());
+        self.sequencer.highest_published(lowest + 1, cursor)
+    }
+
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> DisruptorMetrics {
+        let cursor = self.sequencer.cursor().get();
+        let processors = self
+            .repository
+            .get_processors()
+            .iter()
+            .map(|processor| {
+                let (name, index) = match processor.name() {
+                    Some((name, index)) => (Some(name.to_string()), index),
+                    None => (None, 0),
+                };
+                let sequence = processor.sequence().get();
+                let metrics = processor.metrics();
+                ProcessorSnapshot {
+                    name,
+                    index,
+                    sequence,
+                    lag: cursor - sequence,
+                    events: metrics.as_ref().map_or(0, |m| m.events()),
+                    batch_sizes: metrics.map(|m| m.batch_sizes()).unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        DisruptorMetrics {
+            cursor,
+            processors,
+            sequencer: self
+                .sequencer
+                .metrics()
+                .map(|m| m.snapshot())
+                .unwrap_or_default(),
+        }
+    }
+
+    #[cfg(feature = "latency")]
+    pub(crate) fn with_latency_tracer(mut self, latency: Option<Arc<LatencyTracer>>) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    // Empty unless the builder enabled latency tracing.
+    #[cfg(feature = "latency")]
+    pub fn latency(&self) -> Vec<StageLatency> {
+        self.latency
+            .as_ref()
+            .map(|latency| latency.stages())
+            .unwrap_or_default()
+    }
+
+    pub fn exporter(&self, name: &str) -> DisruptorExporter<S> {
+        DisruptorExporter::new(name, self.sequencer.clone(), &self.repository)
     }
 
     pub fn ringbuffer(&self) -> Arc<RingBuffer<E>> {
@@ -63,6 +218,14 @@ where
         &self.executor
     }
 
+    pub fn remaining_capacity(&self) -> i64 {
+        self.sequencer.remaining_capacity()
+    }
+
+    pub fn has_available_capacity(&self, required: i64) -> bool {
+        self.sequencer.has_available_capacity(required)
+    }
+
     pub fn has_backlog(&mut self) -> bool {
         let cursor = self.sequencer.cursor().get();
         for processor in self.repository.get_processors() {
@@ -70,6 +233,249 @@ where
                 return true;
             }
         }
+        for stream in self.repository.get_streams() {
+            if cursor > stream.sequence.get() {
+                return true;
+            }
+        }
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+
+    use super::*;
+    use crate::{
+        DisruptorBuilder, EventHandler,
+        executor::TokioExecutor,
+        test_support::{SHUTDOWN_TIMEOUT, SumHandler, TestFactory, test_builder},
+        wait_strategy::BlockingWaitStrategy,
+    };
+
+    #[test]
+    fn test_shutdown_drains_backlog() {
+        let sum = Arc::new(AtomicI64::new(0));
+        let (mut disruptor, mut producer) = test_builder()
+            .handler(SumHandler { sum: sum.clone() })
+            .build_with_producer();
+
+        disruptor.start();
+        for i in 1..=100 {
+            producer.publish(|e| *e = i);
+        }
+
+        assert_eq!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT), Ok(()));
+        assert_eq!(sum.load(Ordering::Relaxed), 5050);
+    }
+
+    struct SlowHandler {
+        in_event: Arc<AtomicBool>,
+    }
+
+    impl EventHandler<i64> for SlowHandler {
+        fn on_event(&mut self, _event: &mut i64, _sequence: i64, _end_of_batch: bool) {
+            self.in_event.store(true, Ordering::Release);
+            thread::sleep(Duration::from_millis(50));
+            self.in_event.store(false, Ordering::Release);
+        }
+    }
+
+    #[test]
+    fn test_stop_waits_for_processors_to_exit() {
+        let in_event = Arc::new(AtomicBool::new(false));
+        let (mut disruptor, mut producer) = test_builder()
+            .handler(SlowHandler {
+                in_event: in_event.clone(),
+            })
+            .build_with_producer();
+
+        disruptor.start();
+        producer.publish(|e| *e = 1);
+        while !in_event.load(Ordering::Acquire) {
+            thread::yield_now();
+        }
+
+        disruptor.stop();
+
+        assert!(!in_event.load(Ordering::Acquire));
+        assert!(disruptor.handles.is_empty());
+    }
+
+    #[test]
+    fn test_shutdown_timeout_reports_pending_events() {
+        let sum = Arc::new(AtomicI64::new(0));
+        let (mut disruptor, mut producer) = test_builder()
+            .handler(SumHandler { sum: sum.clone() })
+            .build_with_producer();
+
+        producer.publish(|e| *e = 1);
+
+        assert_eq!(
+            disruptor.shutdown_timeout(Duration::from_millis(10)),
+            Err(ShutdownTimeout)
+        );
+        assert!(disruptor.has_backlog());
+    }
+
+    #[test]
+    fn test_poller_attached_while_publishing() {
+        const EVENTS: i64 = 5_000;
+        let sum = Arc::new(AtomicI64::new(0));
+        let (mut disruptor, producer) = DisruptorBuilder::new_multi_producer(
+            64,
+            TestFactory,
+            TokioExecutor::new(1),
+            BlockingWaitStrategy::new(),
+        )
+        .handler(SumHandler { sum: sum.clone() })
+        .build_with_producer();
+        disruptor.start();
+
+        let published = Arc::new(AtomicI64::new(0));
+        let publishers: Vec<_> = (0..2)
+            .map(|id| {
+                let mut producer = producer.clone();
+                let published = published.clone();
+                thread::spawn(move || {
+                    for count in 0..EVENTS {
+                        producer.publish(|e| *e = id * EVENTS + count);
+                        published.fetch_add(1, Ordering::Release);
+                    }
+                })
+            })
+            .collect();
+
+        while published.load(Ordering::Acquire) < 1_000 {
+            thread::yield_now();
+        }
+        let mut poller = disruptor.poller();
+
+        // A poller seeded past a claimed but unpublished slot would skip it
+        // and leave a hole in that publisher's run of values.
+        let mut last_seen = [None::<i64>; 2];
+        while last_seen.iter().any(|last| *last != Some(EVENTS - 1)) {
+            poller.poll(|event, _, _| {
+                let (id, count) = ((*event / EVENTS) as usize, *event % EVENTS);
+                if let Some(last) = last_seen[id] {
+                    assert_eq!(count, last + 1);
+                }
+                last_seen[id] = Some(count);
+                true
+            });
+        }
+
+        for publisher in publishers {
+            publisher.join().unwrap();
+        }
+        assert_eq!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT), Ok(()));
+    }
+
+    #[cfg(feature = "latency")]
+    #[test]
+    fn test_latency_tracing_records_each_stage() {
+        let sum = Arc::new(AtomicI64::new(0));
+        let (mut disruptor, mut producer) = DisruptorBuilder::new_single_producer(
+            8,
+            TestFactory,
+            TokioExecutor::new(2),
+            BlockingWaitStrategy::new(),
+        )
+        .with_latency_tracing()
+        .handler_named("sum", SumHandler { sum: sum.clone() })
+        .then(SumHandler { sum: sum.clone() })
+        .build_with_producer();
+
+        disruptor.start();
+        for i in 1..=10 {
+            producer.publish(|e| *e = i);
+        }
+        producer.publish_batch(2, |_, e| *e = 0).unwrap();
+        assert_eq!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT), Ok(()));
+
+        let stages = disruptor.latency();
+        let names: Vec<_> = stages.iter().map(|stage| stage.name.as_str()).collect();
+        assert_eq!(names, vec!["sum-0", "stage-1"]);
+        for stage in stages {
+            assert_eq!(stage.histogram.count(), 12);
+            assert!(stage.histogram.value_at_quantile(0.5) <= stage.histogram.max());
+        }
+    }
+
+    #[cfg(feature = "latency")]
+    #[test]
+    fn test_latency_tracing_covers_handlers_registered_before_it() {
+        let sum = Arc::new(AtomicI64::new(0));
+        let (mut disruptor, mut producer) = DisruptorBuilder::new_single_producer(
+            8,
+            TestFactory,
+            TokioExecutor::new(2),
+            BlockingWaitStrategy::new(),
+        )
+        .handler_named("sum", SumHandler { sum: sum.clone() })
+        .with_latency_tracing()
+        .then(SumHandler { sum: sum.clone() })
+        .build_with_producer();
+
+        disruptor.start();
+        for i in 1..=10 {
+            producer.publish(|e| *e = i);
+        }
+        assert_eq!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT), Ok(()));
+
+        let stages = disruptor.latency();
+        let names: Vec<_> = stages.iter().map(|stage| stage.name.as_str()).collect();
+        assert_eq!(names, vec!["sum-0", "stage-1"]);
+        for stage in stages {
+            assert_eq!(stage.histogram.count(), 10);
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_metrics_snapshot() {
+        let sum = Arc::new(AtomicI64::new(0));
+        let (mut disruptor, mut producer) = test_builder()
+            .handler_named("sum", SumHandler { sum: sum.clone() })
+            .build_with_producer();
+
+        for i in 1..=5 {
+            producer.publish(|e| *e = i);
+        }
+        let metrics = disruptor.metrics();
+        assert_eq!(metrics.cursor, 4);
+        assert_eq!(metrics.sequencer.claims, 5);
+        assert_eq!(metrics.processors[0].lag, 5);
+
+        disruptor.start();
+        assert_eq!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT), Ok(()));
+
+        let metrics = disruptor.metrics();
+        let processor = &metrics.processors[0];
+        assert_eq!(processor.name.as_deref(), Some("sum"));
+        assert_eq!(processor.lag, 0);
+        assert_eq!(processor.events, 5);
+        assert!(processor.batch_sizes.count() >= 1);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_worker_pool_metrics_record_batch_sizes() {
+        let sum = Arc::new(AtomicI64::new(0));
+        let (mut disruptor, mut producer) = test_builder()
+            .handler_with_worker_pool([SumHandler { sum: sum.clone() }])
+            .build_with_producer();
+
+        for i in 1..=5 {
+            producer.publish(|e| *e = i);
+        }
+        disruptor.start();
+        assert_eq!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT), Ok(()));
+
+        let processor = &disruptor.metrics().processors[0];
+        assert_eq!(processor.events, 5);
+        assert_eq!(processor.batch_sizes.count(), 1);
+        assert_eq!(processor.batch_sizes.buckets()[2], 1);
+    }
+}
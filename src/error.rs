@@ -0,0 +1,132 @@
+use std::{error::Error, fmt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitError {
+    Alerted,
+    Timeout,
+}
+
+impl fmt::Display for WaitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WaitError::Alerted => write!(f, "sequence barrier alerted"),
+            WaitError::Timeout => write!(f, "timed out waiting for sequence"),
+        }
+    }
+}
+
+impl Error for WaitError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InsufficientCapacity;
+
+impl fmt::Display for InsufficientCapacity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "insufficient capacity in ring buffer")
+    }
+}
+
+impl Error for InsufficientCapacity {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchTooLarge {
+    pub size: usize,
+    pub buffer_size: usize,
+}
+
+impl fmt::Display for BatchTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "batch of {} events exceeds buffer size {}",
+            self.size, self.buffer_size
+        )
+    }
+}
+
+impl Error for BatchTooLarge {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidCore {
+    pub core: usize,
+    pub available: usize,
+}
+
+impl fmt::Display for InvalidCore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "core {} is out of range, only {} cores are available",
+            self.core, self.available
+        )
+    }
+}
+
+impl Error for InvalidCore {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShutdownTimeout;
+
+impl fmt::Display for ShutdownTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "timed out waiting for processors to drain backlog")
+    }
+}
+
+impl Error for ShutdownTimeout {}
+
+#[derive(Debug)]
+pub struct HandlerError {
+    error: Box<dyn Error + Send + Sync>,
+}
+
+impl HandlerError {
+    pub fn new<T>(error: T) -> Self
+    where
+        T: Into<Box<dyn Error + Send + Sync>>,
+    {
+        HandlerError {
+            error: error.into(),
+        }
+    }
+}
+
+impl fmt::Display for HandlerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl Error for HandlerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.error)
+    }
+}
+
+#[derive(Debug)]
+pub struct RewindableError {
+    error: Box<dyn Error + Send + Sync>,
+}
+
+impl RewindableError {
+    pub fn new<T>(error: T) -> Self
+    where
+        T: Into<Box<dyn Error + Send + Sync>>,
+    {
+        RewindableError {
+            error: error.into(),
+        }
+    }
+}
+
+impl fmt::Display for RewindableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "batch rewind requested: {}", self.error)
+    }
+}
+
+impl Error for RewindableError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.error)
+    }
+}
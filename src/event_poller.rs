@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use crate::{RingBuffer, Sequence, Sequencer, SequencerAdapter, barrier, util};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollState {
+    Processing,
+    Gating,
+    Idle,
+}
+
+pub struct EventPoller<E, S>
+where
+    S: Sequencer,
+{
+    ringbuffer: Arc<RingBuffer<E>>,
+    sequencer: SequencerAdapter<S>,
+    sequence: Arc<Sequence>,
+    dependent_sequence: Vec<Arc<Sequence>>,
+}
+
+unsafe impl<E: Send, S: Sequencer> Send for EventPoller<E, S> {}
+
+impl<E, S> EventPoller<E, S>
+where
+    S: Sequencer,
+{
+    pub fn new(
+        ringbuffer: Arc<RingBuffer<E>>,
+        sequencer: SequencerAdapter<S>,
+        sequence: Arc<Sequence>,
+        mut dependent_sequence: Vec<Arc<Sequence>>,
+    ) -> Self {
+        if dependent_sequence.is_empty() {
+            dependent_sequence.push(sequencer.cursor());
+        }
+        EventPoller {
+            ringbuffer,
+            sequencer,
+            sequence,
+            dependent_sequence,
+        }
+    }
+
+    // The handler returns false to stop early; the sequences processed so far
+    // are released to the producer either way.
+    pub fn poll<F>(&mut self, mut handler: F) -> PollState
+    where
+        F: FnMut(&mut E, i64, bool) -> bool,
+    {
+        let current_sequence = self.sequence.get();
+        let mut next_sequence = current_sequence + 1;
+        let available_sequence = barrier::highest_available(
+            &self.sequencer,
+            next_sequence,
+            util::minimum_sequence(&self.dependent_sequence),
+        );
+
+        if next_sequence <= available_sequence {
+            let mut processed_sequence = current_sequence;
+            while next_sequence <= available_sequence {
+                let event = self.ringbuffer.get_mut(next_sequence as usize);
+                let process_next =
+                    handler(event, next_sequence, next_sequence == available_sequence);
+                processed_sequence = next_sequence;
+                next_sequence += 1;
+                if !process_next {
+                    break;
+                }
+            }
+            self.sequence.set(processed_sequence);
+            PollState::Processing
+        } else if self.sequencer.cursor().get() >= next_sequence {
+            PollState::Gating
+        } else {
+            PollState::Idle
+        }
+    }
+
+    pub fn sequence(&self) -> Arc<Sequence> {
+        self.sequence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        SingleProducer, SingleProducerSequencer, test_support::TestFactory,
+        wait_strategy::BusySpinWaitStrategy,
+    };
+
+    type TestSequencer = SingleProducerSequencer<BusySpinWaitStrategy>;
+
+    fn poller() -> (
+        EventPoller<i64, TestSequencer>,
+        SingleProducer<i64, TestSequencer>,
+        Arc<Sequence>,
+    ) {
+        let sequencer = SequencerAdapter::new(SingleProducerSequencer::new(
+            4,
+            Arc::new(BusySpinWaitStrategy {}),
+        ));
+        let ringbuffer = Arc::new(RingBuffer::new(4, TestFactory));
+        let producer = SingleProducer::new(sequencer.clone(), ringbuffer.clone());
+        let upstream = Arc::new(Sequence::new());
+        let poller = EventPoller::new(
+            ringbuffer,
+            sequencer,
+            Arc::new(Sequence::new()),
+            vec![upstream.clone()],
+        );
+        (poller, producer, upstream)
+    }
+
+    #[test]
+    fn test_poll_states() {
+        let (mut poller, mut producer, upstream) = poller();
+        assert_eq!(poller.poll(|_, _, _| true), PollState::Idle);
+
+        producer.publish(|e| *e = 7);
+        assert_eq!(poller.poll(|_, _, _| true), PollState::Gating);
+
+        upstream.set(0);
+        let mut seen = Vec::new();
+        let state = poller.poll(|e, sequence, end_of_batch| {
+            seen.push((*e, sequence, end_of_batch));
+            true
+        });
+        assert_eq!(state, PollState::Processing);
+        assert_eq!(seen, vec![(7, 0, true)]);
+        assert_eq!(poller.sequence().get(), 0);
+        assert_eq!(poller.poll(|_, _, _| true), PollState::Idle);
+    }
+
+    #[test]
+    fn test_poll_stops_when_handler_returns_false() {
+        let (mut poller, mut producer, upstream) = poller();
+        producer.publish_batch(3, |_, e| *e = 1).unwrap();
+        upstream.set(2);
+
+        assert_eq!(poller.poll(|_, _, _| false), PollState::Processing);
+        assert_eq!(poller.sequence().get(), 0);
+        assert_eq!(poller.poll(|_, _, _| true), PollState::Processing);
+        assert_eq!(poller.sequence().get(), 2);
+    }
+}
@@ -0,0 +1,303 @@
+use std::{
+    ops::Deref,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use futures_core::Stream;
+
+use crate::{RingBuffer, Sequence, SequenceBarrier, WaitError};
+
+type WaitFuture = Pin<Box<dyn Future<Output = Result<i64, WaitError>> + Send>>;
+
+// Tracks the refs handed out by a stream. The gating sequence only moves once
+// every ref has been dropped, so a slot can't be reused while it is borrowed.
+struct Release {
+    sequence: Arc<Sequence>,
+    state: Mutex<(usize, i64)>,
+}
+
+impl Release {
+    fn acquire(&self, sequence: i64) {
+        let mut state = self.state.lock().unwrap();
+        state.0 += 1;
+        state.1 = sequence;
+    }
+
+    fn release(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.0 -= 1;
+        if state.0 == 0 {
+            self.sequence.set(state.1);
+        }
+    }
+}
+
+pub struct EventRef<E> {
+    ringbuffer: Arc<RingBuffer<E>>,
+    release: Arc<Release>,
+    sequence: i64,
+    end_of_batch: bool,
+}
+
+unsafe impl<E: Sync> Send for EventRef<E> {}
+unsafe impl<E: Sync> Sync for EventRef<E> {}
+
+impl<E> EventRef<E> {
+    pub fn sequence(&self) -> i64 {
+        self.sequence
+    }
+
+    pub fn end_of_batch(&self) -> bool {
+        self.end_of_batch
+    }
+}
+
+impl<E> Deref for EventRef<E> {
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        self.ringbuffer.get(self.sequence as usize)
+    }
+}
+
+impl<E> Drop for EventRef<E> {
+    fn drop(&mut self) {
+        self.release.release();
+    }
+}
+
+pub struct EventStream<E, B>
+where
+    B: SequenceBarrier,
+{
+    ringbuffer: Arc<RingBuffer<E>>,
+    sequence_barrier: Arc<B>,
+    release: Arc<Release>,
+    next_sequence: i64,
+    available_sequence: i64,
+    wait: Option<WaitFuture>,
+}
+
+unsafe impl<E: Sync, B: SequenceBarrier> Send for EventStream<E, B> {}
+
+impl<E, B> EventStream<E, B>
+where
+    B: SequenceBarrier + 'static,
+{
+    pub fn new(
+        ringbuffer: Arc<RingBuffer<E>>,
+        sequence_barrier: Arc<B>,
+        sequence: Arc<Sequence>,
+    ) -> Self {
+        let next_sequence = sequence.get() + 1;
+        EventStream {
+            ringbuffer,
+            sequence_barrier,
+            release: Arc::new(Release {
+                state: Mutex::new((0, sequence.get())),
+                sequence,
+            }),
+            next_sequence,
+            available_sequence: next_sequence - 1,
+            wait: None,
+        }
+    }
+
+    pub fn sequence(&self) -> Arc<Sequence> {
+        self.release.sequence.clone()
+    }
+
+    pub fn cloned(self) -> ClonedEventStream<E, B>
+    where
+        E: Clone,
+    {
+        ClonedEventStream { stream: self }
+    }
+}
+
+impl<E, B> Stream for EventStream<E, B>
+where
+    B: SequenceBarrier + 'static,
+{
+    type Item = EventRef<E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<EventRef<E>>> {
+        let this = self.get_mut();
+
+        if this.next_sequence > this.available_sequence {
+            let wait = this.wait.get_or_insert_with(|| {
+                let sequence_barrier = this.sequence_barrier.clone();
+                let next_sequence = this.next_sequence;
+                Box::pin(async move { sequence_barrier.wait_for_async(next_sequence).await })
+            });
+
+            match wait.as_mut().poll(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(result) => {
+                    this.wait = None;
+                    match result {
+                        Ok(sequence) => this.available_sequence = sequence,
+                        Err(WaitError::Timeout) => {}
+                        Err(WaitError::Alerted) => return Poll::Ready(None),
+                    }
+                }
+            }
+
+            if this.next_sequence > this.available_sequence {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+        }
+
+        let sequence = this.next_sequence;
+        this.next_sequence += 1;
+        this.release.acquire(sequence);
+
+        Poll::Ready(Some(EventRef {
+            ringbuffer: this.ringbuffer.clone(),
+            release: this.release.clone(),
+            sequence,
+            end_of_batch: sequence == this.available_sequence,
+        }))
+    }
+}
+
+pub struct ClonedEventStream<E, B>
+where
+    B: SequenceBarrier,
+{
+    stream: EventStream<E, B>,
+}
+
+impl<E, B> Stream for ClonedEventStream<E, B>
+where
+    E: Clone,
+    B: SequenceBarrier + 'static,
+{
+    type Item = E;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<E>> {
+        Pin::new(&mut self.stream)
+            .poll_next(cx)
+            .map(|event| event.map(|event| (*event).clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future::poll_fn, thread, time::Duration};
+
+    use super::*;
+    use crate::{
+        DisruptorBuilder,
+        executor::TokioExecutor,
+        test_support::{NoopHandler, TestFactory},
+        util::block_on,
+        wait_strategy::AsyncWaitStrategy,
+    };
+
+    fn next<S: Stream + Unpin>(stream: &mut S) -> Option<S::Item> {
+        block_on(poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)))
+    }
+
+    #[test]
+    fn test_stream_yields_published_events() {
+        let mut builder = DisruptorBuilder::new_single_producer(
+            4,
+            TestFactory,
+            TokioExecutor::new(1),
+            AsyncWaitStrategy::new(),
+        );
+        let handle = builder.handle(NoopHandler);
+        let dependencies = vec![handle];
+        let mut stream = builder.after(&dependencies).stream();
+        let (mut disruptor, mut producer) = builder.build_with_producer();
+        disruptor.start();
+
+        let publisher = thread::spawn(move || {
+            for i in 0..10 {
+                producer.publish(|e| *e = i);
+            }
+        });
+
+        for i in 0..10 {
+            let event = next(&mut stream).unwrap();
+            assert_eq!(*event, i);
+            assert_eq!(event.sequence(), i);
+        }
+        publisher.join().unwrap();
+        assert_eq!(stream.sequence().get(), 9);
+        disruptor.stop();
+    }
+
+    #[test]
+    fn test_held_ref_keeps_slot_gated() {
+        let mut builder = DisruptorBuilder::new_single_producer(
+            4,
+            TestFactory,
+            TokioExecutor::new(1),
+            AsyncWaitStrategy::new(),
+        );
+        let mut stream = builder.stream();
+        let (_disruptor, mut producer) = builder.build_with_producer();
+
+        producer
+            .publish_batch(2, |sequence, e| *e = sequence)
+            .unwrap();
+        let first = next(&mut stream).unwrap();
+        let second = next(&mut stream).unwrap();
+        drop(second);
+        assert_eq!(stream.sequence().get(), -1);
+        drop(first);
+        assert_eq!(stream.sequence().get(), 1);
+
+        producer.publish(|e| *e = 42);
+        let mut cloned = stream.cloned();
+        assert_eq!(next(&mut cloned), Some(42));
+    }
+
+    #[test]
+    fn test_stop_ends_pending_stream() {
+        let mut builder = DisruptorBuilder::new_single_producer(
+            4,
+            TestFactory,
+            TokioExecutor::new(1),
+            AsyncWaitStrategy::new(),
+        );
+        let mut stream = builder.stream();
+        let (mut disruptor, mut producer) = builder.build_with_producer();
+        disruptor.start();
+
+        producer.publish(|e| *e = 7);
+        assert!(disruptor.has_backlog());
+        assert_eq!(next(&mut stream).map(|event| *event), Some(7));
+        assert!(!disruptor.has_backlog());
+
+        let consumer = thread::spawn(move || next(&mut stream).is_none());
+        thread::sleep(Duration::from_millis(20));
+        disruptor.stop();
+        assert!(consumer.join().unwrap());
+    }
+
+    #[test]
+    fn test_empty_stream_poll_returns_pending() {
+        let mut builder = DisruptorBuilder::new_single_producer(
+            4,
+            TestFactory,
+            TokioExecutor::new(1),
+            AsyncWaitStrategy::new(),
+        );
+        let mut stream = builder.stream();
+        let (_disruptor, mut producer) = builder.build_with_producer();
+
+        let polled = block_on(poll_fn(|cx| {
+            Poll::Ready(Pin::new(&mut stream).poll_next(cx).is_pending())
+        }));
+        assert!(polled);
+
+        producer.publish(|e| *e = 3);
+        assert_eq!(next(&mut stream).map(|event| *event), Some(3));
+    }
+}
@@ -0,0 +1,408 @@
+use std::{
+    any::Any,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use crate::{ExceptionAction, ExceptionHandler};
+
+pub fn panic_message(error: &(dyn Any + Send)) -> &str {
+    if let Some(message) = error.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = error.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    }
+}
+
+pub struct LogAndSkipExceptionHandler;
+
+impl<E> ExceptionHandler<E> for LogAndSkipExceptionHandler {
+    fn handle_event_exception(
+        &self,
+        error: &(dyn Any + Send),
+        sequence: i64,
+        _event: &mut E,
+    ) -> ExceptionAction {
+        log::error!(
+            "exception processing sequence {}: {}",
+            sequence,
+            panic_message(error)
+        );
+        ExceptionAction::Continue
+    }
+
+    fn handle_on_start_exception(&self, error: &(dyn Any + Send)) -> ExceptionAction {
+        log::error!("exception during on_start: {}", panic_message(error));
+        ExceptionAction::Continue
+    }
+
+    fn handle_on_shutdown_exception(&self, error: &(dyn Any + Send)) {
+        log::error!("exception during on_shutdown: {}", panic_message(error));
+    }
+
+    fn handle_on_timeout_exception(
+        &self,
+        error: &(dyn Any + Send),
+        sequence: i64,
+    ) -> ExceptionAction {
+        log::error!(
+            "exception during on_timeout after sequence {}: {}",
+            sequence,
+            panic_message(error)
+        );
+        ExceptionAction::Continue
+    }
+}
+
+pub struct HaltProcessorExceptionHandler;
+
+impl<E> ExceptionHandler<E> for HaltProcessorExceptionHandler {
+    fn handle_event_exception(
+        &self,
+        error: &(dyn Any + Send),
+        sequence: i64,
+        _event: &mut E,
+    ) -> ExceptionAction {
+        log::error!(
+            "exception processing sequence {}, halting processor: {}",
+            sequence,
+            panic_message(error)
+        );
+        ExceptionAction::HaltProcessor
+    }
+
+    fn handle_on_start_exception(&self, error: &(dyn Any + Send)) -> ExceptionAction {
+        log::error!(
+            "exception during on_start, halting processor: {}",
+            panic_message(error)
+        );
+        ExceptionAction::HaltProcessor
+    }
+
+    fn handle_on_shutdown_exception(&self, error: &(dyn Any + Send)) {
+        log::error!("exception during on_shutdown: {}", panic_message(error));
+    }
+
+    fn handle_on_timeout_exception(
+        &self,
+        error: &(dyn Any + Send),
+        sequence: i64,
+    ) -> ExceptionAction {
+        log::error!(
+            "exception during on_timeout after sequence {}, halting processor: {}",
+            sequence,
+            panic_message(error)
+        );
+        ExceptionAction::HaltProcessor
+    }
+}
+
+pub struct HaltDisruptorExceptionHandler;
+
+impl<E> ExceptionHandler<E> for HaltDisruptorExceptionHandler {
+    fn handle_event_exception(
+        &self,
+        error: &(dyn Any + Send),
+        sequence: i64,
+        _event: &mut E,
+    ) -> ExceptionAction {
+        log::error!(
+            "exception processing sequence {}, halting disruptor: {}",
+            sequence,
+            panic_message(error)
+        );
+        ExceptionAction::HaltDisruptor
+    }
+
+    fn handle_on_start_exception(&self, error: &(dyn Any + Send)) -> ExceptionAction {
+        log::error!(
+            "exception during on_start, halting disruptor: {}",
+            panic_message(error)
+        );
+        ExceptionAction::HaltDisruptor
+    }
+
+    fn handle_on_shutdown_exception(&self, error: &(dyn Any + Send)) {
+        log::error!("exception during on_shutdown: {}", panic_message(error));
+    }
+
+    fn handle_on_timeout_exception(
+        &self,
+        error: &(dyn Any + Send),
+        sequence: i64,
+    ) -> ExceptionAction {
+        log::error!(
+            "exception during on_timeout after sequence {}, halting disruptor: {}",
+            sequence,
+            panic_message(error)
+        );
+        ExceptionAction::HaltDisruptor
+    }
+}
+
+// The builder keeps one wrapper for its default and gives each processor
+// another that delegates to it, so replacing either delegate covers
+// processors registered before the replacement too.
+pub(crate) struct ExceptionHandlerWrapper<E> {
+    delegate: RwLock<Arc<dyn ExceptionHandler<E>>>,
+}
+
+impl<E: 'static> ExceptionHandlerWrapper<E> {
+    pub(crate) fn new() -> Self {
+        Self::delegating_to(Arc::new(HaltProcessorExceptionHandler))
+    }
+
+    pub(crate) fn delegating_to(delegate: Arc<dyn ExceptionHandler<E>>) -> Self {
+        ExceptionHandlerWrapper {
+            delegate: RwLock::new(delegate),
+        }
+    }
+
+    pub(crate) fn switch_to(&self, exception_handler: Arc<dyn ExceptionHandler<E>>) {
+        *self.delegate.write().unwrap() = exception_handler;
+    }
+
+    fn delegate(&self) -> Arc<dyn ExceptionHandler<E>> {
+        self.delegate.read().unwrap().clone()
+    }
+}
+
+impl<E: 'static> ExceptionHandler<E> for ExceptionHandlerWrapper<E> {
+    fn handle_event_exception(
+        &self,
+        error: &(dyn Any + Send),
+        sequence: i64,
+        event: &mut E,
+    ) -> ExceptionAction {
+        self.delegate()
+            .handle_event_exception(error, sequence, event)
+    }
+
+    fn handle_on_start_exception(&self, error: &(dyn Any + Send)) -> ExceptionAction {
+        self.delegate().handle_on_start_exception(error)
+    }
+
+    fn handle_on_shutdown_exception(&self, error: &(dyn Any + Send)) {
+        self.delegate().handle_on_shutdown_exception(error);
+    }
+
+    fn handle_on_timeout_exception(
+        &self,
+        error: &(dyn Any + Send),
+        sequence: i64,
+    ) -> ExceptionAction {
+        self.delegate().handle_on_timeout_exception(error, sequence)
+    }
+}
+
+type Halter = Box<dyn Fn() + Send + Sync>;
+
+#[derive(Clone, Default)]
+pub(crate) struct HaltGroup {
+    halters: Arc<Mutex<Vec<Halter>>>,
+}
+
+impl HaltGroup {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn add<F>(&self, halter: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.halters.lock().unwrap().push(Box::new(halter));
+    }
+
+    pub(crate) fn halt_all(&self) {
+        for halter in self.halters.lock().unwrap().iter() {
+            halter();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{
+        DisruptorBuilder, EventHandler, WorkHandler,
+        executor::TokioExecutor,
+        test_support::{SHUTDOWN_TIMEOUT, TestFactory, test_builder},
+        wait_strategy::BlockingWaitStrategy,
+    };
+
+    struct PanickingHandler {
+        panic_at: i64,
+        count: Arc<AtomicUsize>,
+    }
+
+    impl EventHandler<i64> for PanickingHandler {
+        fn on_event(&mut self, _event: &mut i64, sequence: i64, _end_of_batch: bool) {
+            if sequence == self.panic_at {
+                panic!("boom at {}", sequence);
+            }
+            self.count.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_log_and_skip_continues_after_panic() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let (mut disruptor, mut producer) = test_builder()
+            .with_exception_handler(LogAndSkipExceptionHandler)
+            .handler(PanickingHandler {
+                panic_at: 3,
+                count: count.clone(),
+            })
+            .build_with_producer();
+
+        disruptor.start();
+        for i in 0..10 {
+            producer.publish(|e| *e = i);
+        }
+
+        assert!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT).is_ok());
+        assert_eq!(count.load(Ordering::Relaxed), 9);
+    }
+
+    #[test]
+    fn test_halt_disruptor_halts_every_processor() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let (mut disruptor, mut producer) = DisruptorBuilder::new_single_producer(
+            8,
+            TestFactory,
+            TokioExecutor::new(2),
+            BlockingWaitStrategy::new(),
+        )
+        .with_exception_handler(HaltDisruptorExceptionHandler)
+        .handler(PanickingHandler {
+            panic_at: 2,
+            count: count.clone(),
+        })
+        .and(PanickingHandler {
+            panic_at: -1,
+            count: count.clone(),
+        })
+        .build_with_producer();
+
+        disruptor.start();
+        for i in 0..5 {
+            producer.publish(|e| *e = i);
+        }
+
+        disruptor.join();
+        assert!(disruptor.has_backlog());
+    }
+
+    #[test]
+    fn test_exception_handler_can_be_overridden_per_handler() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let mut builder = DisruptorBuilder::new_single_producer(
+            16,
+            TestFactory,
+            TokioExecutor::new(2),
+            BlockingWaitStrategy::new(),
+        );
+        let skipping = builder.handle(PanickingHandler {
+            panic_at: 2,
+            count: count.clone(),
+        });
+        let halting = builder.handle(PanickingHandler {
+            panic_at: 2,
+            count: Arc::new(AtomicUsize::new(0)),
+        });
+        builder
+            .handle_exceptions_for(&skipping)
+            .with(LogAndSkipExceptionHandler);
+        let (mut disruptor, mut producer) = builder.build_with_producer();
+
+        disruptor.start();
+        for i in 0..10 {
+            producer.publish(|e| *e = i);
+        }
+        while skipping.sequence().get() < 9 || halting.sequence().get() < 1 {
+            std::thread::yield_now();
+        }
+        disruptor.stop();
+
+        assert_eq!(count.load(Ordering::Relaxed), 9);
+        assert_eq!(halting.sequence().get(), 1);
+    }
+
+    struct PanickingWorkHandler {
+        count: Arc<AtomicUsize>,
+    }
+
+    impl WorkHandler<i64> for PanickingWorkHandler {
+        fn on_event(&mut self, event: &mut i64) {
+            if *event == 3 {
+                panic!("boom at {}", event);
+            }
+            self.count.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_worker_panic_goes_to_handler_set_after_registration() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let (mut disruptor, mut producer) = DisruptorBuilder::new_single_producer(
+            8,
+            TestFactory,
+            TokioExecutor::new(2),
+            BlockingWaitStrategy::new(),
+        )
+        .handler_with_worker_pool((0..2).map(|_| PanickingWorkHandler {
+            count: count.clone(),
+        }))
+        .with_exception_handler(LogAndSkipExceptionHandler)
+        .build_with_producer();
+
+        disruptor.start();
+        for i in 0..20 {
+            producer.publish(|e| *e = i);
+        }
+
+        assert!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT).is_ok());
+        assert_eq!(count.load(Ordering::Relaxed), 19);
+    }
+
+    struct PanickingLifecycleWorkHandler {
+        count: Arc<AtomicUsize>,
+    }
+
+    impl WorkHandler<i64> for PanickingLifecycleWorkHandler {
+        fn on_event(&mut self, _event: &mut i64) {
+            self.count.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_start(&mut self) {
+            panic!("on_start failed");
+        }
+
+        fn on_shutdown(&mut self) {
+            panic!("on_shutdown failed");
+        }
+    }
+
+    #[test]
+    fn test_worker_lifecycle_panics_go_to_handler() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let (mut disruptor, mut producer) = test_builder()
+            .with_exception_handler(LogAndSkipExceptionHandler)
+            .handler_with_worker_pool([PanickingLifecycleWorkHandler {
+                count: count.clone(),
+            }])
+            .build_with_producer();
+
+        disruptor.start();
+        for i in 0..10 {
+            producer.publish(|e| *e = i);
+        }
+
+        assert!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT).is_ok());
+        assert_eq!(count.load(Ordering::Relaxed), 10);
+    }
+}
@@ -1,48 +1,664 @@
-use async_executor::Executor;
+use std::{
+    future::poll_fn,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    task::{Poll, Waker},
+    thread,
+};
+
+use async_executor::{Executor, Task};
 use tokio::runtime::{self, Runtime};
 
-use crate::Runnable;
+use crate::{
+    InvalidCore, JoinHandle, Runnable,
+    disruptor::{DRAIN_MAX_BACKOFF, DRAIN_MIN_BACKOFF},
+    util,
+};
+
+// Builds the thread name for a named processor from its name and index.
+// Linux keeps only the first 15 bytes of a thread name, so long names can
+// collide there: `disruptor-journal-0` and `disruptor-journal-1` both show
+// up as `disruptor-journ`.
+pub type ThreadNameFn = Arc<dyn Fn(&str, usize) -> String + Send + Sync>;
+
+fn default_thread_name() -> ThreadNameFn {
+    Arc::new(|name, index| format!("disruptor-{name}-{index}"))
+}
+
+fn default_worker_name(index: usize) -> String {
+    format!("disruptor-{index}")
+}
 
+// Pool executors name their worker threads for unnamed processors. A named
+// processor gets a thread of its own, so the name stays visible in tools
+// like `top -H`.
 pub struct TokioExecutor {
     rt: Runtime,
+    thread_name: ThreadNameFn,
 }
 
 impl TokioExecutor {
     pub fn new(worker_thread_num: usize) -> Self {
+        Self::new_with_thread_name(worker_thread_num, default_worker_name)
+    }
+
+    pub fn new_with_thread_name<F>(worker_thread_num: usize, thread_name: F) -> Self
+    where
+        F: Fn(usize) -> String + Send + Sync + 'static,
+    {
+        let next_index = AtomicUsize::new(0);
         let rt = runtime::Builder::new_multi_thread()
             .enable_all()
             .worker_threads(worker_thread_num)
+            .thread_name_fn(move || thread_name(next_index.fetch_add(1, Ordering::Relaxed)))
             .build()
             .unwrap();
-        TokioExecutor { rt }
+        TokioExecutor {
+            rt,
+            thread_name: default_thread_name(),
+        }
+    }
+
+    pub fn with_thread_name<F>(mut self, thread_name: F) -> Self
+    where
+        F: Fn(&str, usize) -> String + Send + Sync + 'static,
+    {
+        self.thread_name = Arc::new(thread_name);
+        self
     }
 }
 
 impl crate::Executor for TokioExecutor {
-    fn execute(&self, mut runnable: Box<dyn Runnable>) {
-        self.rt.spawn(async move {
+    fn execute(&self, mut runnable: Box<dyn Runnable>) -> Box<dyn JoinHandle> {
+        let handle = self.rt.spawn(async move {
             runnable.run().await;
         });
+        Box::new(TokioJoinHandle { handle })
+    }
+
+    // The dedicated thread drives the runnable through the runtime's handle,
+    // so it still has the runtime's timers and IO.
+    fn execute_named(
+        &self,
+        mut runnable: Box<dyn Runnable>,
+        name: &str,
+        index: usize,
+    ) -> Box<dyn JoinHandle> {
+        let rt = self.rt.handle().clone();
+        let handle = thread::Builder::new()
+            .name((self.thread_name)(name, index))
+            .spawn(move || rt.block_on(runnable.run()))
+            .unwrap();
+        Box::new(ThreadJoinHandle { handle })
+    }
+}
+
+pub struct TokioJoinHandle {
+    handle: tokio::task::JoinHandle<()>,
+}
+
+impl JoinHandle for TokioJoinHandle {
+    fn join(self: Box<Self>) {
+        let _ = util::block_on(self.handle);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.handle.is_finished()
     }
 }
 
-pub struct SmolExecutor<'a> {
-    executor: Executor<'a>,
+#[derive(Default)]
+struct ShutdownSignal {
+    stopped: AtomicBool,
+    wakers: Mutex<Vec<Waker>>,
 }
 
-impl<'a> SmolExecutor<'a> {
+impl ShutdownSignal {
+    fn trigger(&self) {
+        self.stopped.store(true, Ordering::Release);
+        for waker in self.wakers.lock().unwrap().drain(..) {
+            waker.wake();
+        }
+    }
+
+    async fn wait(&self) {
+        poll_fn(|cx| {
+            if self.stopped.load(Ordering::Acquire) {
+                return Poll::Ready(());
+            }
+            let mut wakers = self.wakers.lock().unwrap();
+            if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                wakers.push(cx.waker().clone());
+            }
+            // Checked again under the lock so a trigger can't slip in between.
+            if self.stopped.load(Ordering::Acquire) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await
+    }
+}
+
+pub struct SmolExecutor {
+    executor: Arc<Executor<'static>>,
+    running: Arc<AtomicUsize>,
+    shutdown: Arc<ShutdownSignal>,
+    workers: Mutex<Vec<thread::JoinHandle<()>>>,
+    thread_name: ThreadNameFn,
+}
+
+impl SmolExecutor {
+    pub fn new(worker_thread_num: usize) -> Self {
+        Self::new_with_thread_name(worker_thread_num, default_worker_name)
+    }
+
+    pub fn new_with_thread_name<F>(worker_thread_num: usize, thread_name: F) -> Self
+    where
+        F: Fn(usize) -> String + Send + Sync + 'static,
+    {
+        let executor = Arc::new(Executor::new());
+        let shutdown = Arc::new(ShutdownSignal::default());
+
+        let workers = (0..worker_thread_num)
+            .map(|index| {
+                let executor = executor.clone();
+                let shutdown = shutdown.clone();
+                thread::Builder::new()
+                    .name(thread_name(index))
+                    .spawn(move || util::block_on(executor.run(shutdown.wait())))
+                    .unwrap()
+            })
+            .collect();
+
+        SmolExecutor {
+            executor,
+            running: Arc::new(AtomicUsize::new(0)),
+            shutdown,
+            workers: Mutex::new(workers),
+            thread_name: default_thread_name(),
+        }
+    }
+
+    pub fn with_thread_name<F>(mut self, thread_name: F) -> Self
+    where
+        F: Fn(&str, usize) -> String + Send + Sync + 'static,
+    {
+        self.thread_name = Arc::new(thread_name);
+        self
+    }
+
+    // Waits for every processor to halt, then stops the worker threads. Halt
+    // the disruptor first, or this never returns. Backs off like a drain, so
+    // a long wait doesn't keep a core busy.
+    pub fn shutdown(&self) {
+        let mut backoff = DRAIN_MIN_BACKOFF;
+        while self.running.load(Ordering::Acquire) > 0 {
+            thread::sleep(backoff);
+            backoff = (backoff * 2).min(DRAIN_MAX_BACKOFF);
+        }
+        self.stop_workers();
+    }
+
+    fn stop_workers(&self) {
+        self.shutdown.trigger();
+        for worker in self.workers.lock().unwrap().drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Default for SmolExecutor {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl Drop for SmolExecutor {
+    fn drop(&mut self) {
+        self.stop_workers();
+    }
+}
+
+impl crate::Executor for SmolExecutor {
+    fn execute(&self, mut runnable: Box<dyn Runnable>) -> Box<dyn JoinHandle> {
+        let running = RunningGuard::new(&self.running);
+        let task = self.executor.spawn(async move {
+            let _running = running;
+            runnable.run().await;
+        });
+        Box::new(SmolJoinHandle {
+            executor: self.executor.clone(),
+            task,
+        })
+    }
+
+    fn execute_named(
+        &self,
+        mut runnable: Box<dyn Runnable>,
+        name: &str,
+        index: usize,
+    ) -> Box<dyn JoinHandle> {
+        let running = RunningGuard::new(&self.running);
+        let handle = thread::Builder::new()
+            .name((self.thread_name)(name, index))
+            .spawn(move || {
+                let _running = running;
+                util::block_on(runnable.run());
+            })
+            .unwrap();
+        Box::new(ThreadJoinHandle { handle })
+    }
+}
+
+// Counts a runnable as running until dropped, so one that panics still lets
+// `shutdown` return.
+struct RunningGuard {
+    running: Arc<AtomicUsize>,
+}
+
+impl RunningGuard {
+    fn new(running: &Arc<AtomicUsize>) -> Self {
+        running.fetch_add(1, Ordering::AcqRel);
+        RunningGuard {
+            running: running.clone(),
+        }
+    }
+}
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        self.running.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+pub struct SmolJoinHandle {
+    executor: Arc<Executor<'static>>,
+    task: Task<()>,
+}
+
+impl JoinHandle for SmolJoinHandle {
+    fn join(self: Box<Self>) {
+        // Drive the executor from the joining thread as well, so the task can
+        // finish even if nothing else is running it.
+        util::block_on(self.executor.run(self.task));
+    }
+
+    fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+}
+
+pub struct ThreadExecutor {
+    affinity: Vec<usize>,
+    priority: Option<i32>,
+    spawned: AtomicUsize,
+    thread_name: ThreadNameFn,
+}
+
+impl ThreadExecutor {
     pub fn new() -> Self {
-        let executor = Executor::new();
-        SmolExecutor { executor }
+        ThreadExecutor {
+            affinity: Vec::new(),
+            priority: None,
+            spawned: AtomicUsize::new(0),
+            thread_name: default_thread_name(),
+        }
+    }
+
+    pub fn with_thread_name<F>(mut self, thread_name: F) -> Self
+    where
+        F: Fn(&str, usize) -> String + Send + Sync + 'static,
+    {
+        self.thread_name = Arc::new(thread_name);
+        self
+    }
+
+    // Threads are pinned round-robin over the given cores, in spawn order.
+    // Cores are checked here, since a thread can only report a bad one once
+    // it is already running.
+    pub fn with_affinity<I: IntoIterator<Item = usize>>(
+        mut self,
+        cores: I,
+    ) -> Result<Self, InvalidCore> {
+        let available = available_cores();
+        self.affinity = cores.into_iter().collect();
+        if let Some(&core) = self.affinity.iter().find(|&&core| core >= available) {
+            return Err(InvalidCore { core, available });
+        }
+        Ok(self)
+    }
+
+    // A nice value, so lower means higher priority.
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+}
+
+impl Default for ThreadExecutor {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-impl<'a> crate::Executor for SmolExecutor<'a> {
-    fn execute(&self, mut runnable: Box<dyn Runnable>) {
-        self.executor
-            .spawn(async move {
-                runnable.run().await;
+impl ThreadExecutor {
+    fn spawn(
+        &self,
+        mut runnable: Box<dyn Runnable>,
+        name: Option<(&str, usize)>,
+    ) -> Box<dyn JoinHandle> {
+        let index = self.spawned.fetch_add(1, Ordering::Relaxed);
+        let thread_name = match name {
+            Some((name, index)) => (self.thread_name)(name, index),
+            None => format!("disruptor-{index}"),
+        };
+        let core = if self.affinity.is_empty() {
+            None
+        } else {
+            Some(self.affinity[index % self.affinity.len()])
+        };
+        let priority = self.priority;
+
+        let handle = thread::Builder::new()
+            .name(thread_name)
+            .spawn(move || {
+                if let Some(core) = core {
+                    set_affinity(core);
+                }
+                if let Some(priority) = priority {
+                    set_priority(priority);
+                }
+                util::block_on(runnable.run());
+            })
+            .unwrap();
+        Box::new(ThreadJoinHandle { handle })
+    }
+}
+
+impl crate::Executor for ThreadExecutor {
+    fn execute(&self, runnable: Box<dyn Runnable>) -> Box<dyn JoinHandle> {
+        self.spawn(runnable, None)
+    }
+
+    fn execute_named(
+        &self,
+        runnable: Box<dyn Runnable>,
+        name: &str,
+        index: usize,
+    ) -> Box<dyn JoinHandle> {
+        self.spawn(runnable, Some((name, index)))
+    }
+}
+
+pub struct ThreadJoinHandle {
+    handle: thread::JoinHandle<()>,
+}
+
+impl JoinHandle for ThreadJoinHandle {
+    fn join(self: Box<Self>) {
+        let _ = self.handle.join();
+    }
+
+    fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+}
+
+// `CPU_SET` panics past `CPU_SETSIZE`, so that caps the count even on
+// machines with more cores configured.
+#[cfg(target_os = "linux")]
+fn available_cores() -> usize {
+    let configured = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_CONF) };
+    usize::try_from(configured)
+        .unwrap_or(0)
+        .min(libc::CPU_SETSIZE as usize)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn available_cores() -> usize {
+    thread::available_parallelism().map_or(1, |cores| cores.get())
+}
+
+#[cfg(target_os = "linux")]
+fn set_affinity(core: usize) {
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(core, &mut set);
+        if libc::sched_setaffinity(0, size_of::<libc::cpu_set_t>(), &set) != 0 {
+            log::warn!(
+                "failed to pin thread to core {}: {}",
+                core,
+                std::io::Error::last_os_error()
+            );
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn set_affinity(_core: usize) {}
+
+#[cfg(target_os = "linux")]
+fn set_priority(priority: i32) {
+    // With PRIO_PROCESS and 0, Linux applies the nice value to the calling
+    // thread only.
+    unsafe {
+        if libc::setpriority(libc::PRIO_PROCESS, 0, priority) != 0 {
+            log::warn!(
+                "failed to set thread priority {}: {}",
+                priority,
+                std::io::Error::last_os_error()
+            );
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn set_priority(_priority: i32) {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::{
+        DisruptorBuilder, EventHandler, Executor as _, test_support::TestFactory,
+        wait_strategy::BlockingWaitStrategy,
+    };
+
+    struct NameRecorder {
+        names: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Runnable for NameRecorder {
+        fn run(&mut self) -> std::pin::Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+            Box::pin(async move {
+                let name = thread::current().name().unwrap().to_string();
+                self.names.lock().unwrap().push(name);
+            })
+        }
+    }
+
+    struct CountdownRunnable {
+        remaining: Arc<AtomicUsize>,
+    }
+
+    impl Runnable for CountdownRunnable {
+        fn run(&mut self) -> std::pin::Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+            Box::pin(async move {
+                self.remaining.fetch_sub(1, Ordering::AcqRel);
             })
-            .detach();
+        }
+    }
+
+    #[test]
+    fn test_smol_executor_runs_tasks_on_its_workers() {
+        let remaining = Arc::new(AtomicUsize::new(3));
+        let executor = SmolExecutor::new(2);
+
+        // The handles are never joined, so only the worker threads can drive
+        // the tasks.
+        let _handles: Vec<_> = (0..3)
+            .map(|_| {
+                executor.execute(Box::new(CountdownRunnable {
+                    remaining: remaining.clone(),
+                }))
+            })
+            .collect();
+        executor.shutdown();
+
+        assert_eq!(remaining.load(Ordering::Acquire), 0);
+        assert!(executor.workers.lock().unwrap().is_empty());
+    }
+
+    struct PanickingRunnable;
+
+    impl Runnable for PanickingRunnable {
+        fn run(&mut self) -> std::pin::Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+            Box::pin(async { panic!("runnable failed") })
+        }
+    }
+
+    #[test]
+    fn test_smol_shutdown_returns_after_runnable_panics() {
+        let executor = SmolExecutor::new(1);
+
+        let _handle = executor.execute(Box::new(PanickingRunnable));
+        let _named = executor.execute_named(Box::new(PanickingRunnable), "journal", 0);
+        executor.shutdown();
+
+        assert_eq!(executor.running.load(Ordering::Acquire), 0);
+    }
+
+    #[test]
+    fn test_thread_executor_spawns_named_thread_per_runnable() {
+        let names = Arc::new(Mutex::new(Vec::new()));
+        let executor = ThreadExecutor::new().with_affinity([0]).unwrap();
+
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                executor.execute(Box::new(NameRecorder {
+                    names: names.clone(),
+                }))
+            })
+            .collect();
+        for handle in handles {
+            handle.join();
+        }
+
+        let mut names = names.lock().unwrap().clone();
+        names.sort();
+        assert_eq!(names, vec!["disruptor-0", "disruptor-1"]);
+    }
+
+    #[test]
+    fn test_thread_executor_rejects_out_of_range_core() {
+        let available = available_cores();
+        let error = ThreadExecutor::new()
+            .with_affinity([0, available])
+            .err()
+            .unwrap();
+        assert_eq!(
+            error,
+            InvalidCore {
+                core: available,
+                available
+            }
+        );
+        assert!(ThreadExecutor::new().with_affinity([usize::MAX]).is_err());
+    }
+
+    #[test]
+    fn test_pool_executors_name_their_workers() {
+        let names = Arc::new(Mutex::new(Vec::new()));
+        let tokio = TokioExecutor::new_with_thread_name(1, |index| format!("tokio-{index}"));
+        let smol = SmolExecutor::new_with_thread_name(1, |index| format!("smol-{index}"));
+
+        let recorder = || {
+            Box::new(NameRecorder {
+                names: names.clone(),
+            })
+        };
+        tokio.execute(recorder()).join();
+        // Joining a smol handle drives the task from the joining thread, so
+        // let the worker run it and wait through shutdown instead.
+        let _handle = smol.execute(recorder());
+        smol.shutdown();
+
+        let names = names.lock().unwrap().clone();
+        assert_eq!(names, vec!["tokio-0", "smol-0"]);
+    }
+
+    #[test]
+    fn test_pool_executors_give_named_runnables_named_threads() {
+        let names = Arc::new(Mutex::new(Vec::new()));
+        let tokio = TokioExecutor::new(1);
+        let smol =
+            SmolExecutor::new(1).with_thread_name(|name, index| format!("smol-{name}-{index}"));
+
+        let recorder = || {
+            Box::new(NameRecorder {
+                names: names.clone(),
+            })
+        };
+        tokio.execute_named(recorder(), "journal", 0).join();
+        smol.execute_named(recorder(), "journal", 1).join();
+        smol.shutdown();
+
+        let names = names.lock().unwrap().clone();
+        assert_eq!(names, vec!["disruptor-journal-0", "smol-journal-1"]);
+    }
+
+    struct ThreadNameHandler {
+        names: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl EventHandler<i64> for ThreadNameHandler {
+        fn on_start(&mut self) {
+            let name = thread::current().name().unwrap_or_default().to_string();
+            self.names.lock().unwrap().push(name);
+        }
+    }
+
+    #[test]
+    fn test_named_handlers_get_named_threads() {
+        assert_named_handler_gets_named_thread(ThreadExecutor::new());
+        assert_named_handler_gets_named_thread(TokioExecutor::new(1));
+    }
+
+    fn assert_named_handler_gets_named_thread<X: crate::Executor + 'static>(executor: X) {
+        let names = Arc::new(Mutex::new(Vec::new()));
+        let (mut disruptor, _producer) = DisruptorBuilder::new_single_producer(
+            8,
+            TestFactory,
+            executor,
+            BlockingWaitStrategy::new(),
+        )
+        .handler_named(
+            "journal",
+            ThreadNameHandler {
+                names: names.clone(),
+            },
+        )
+        .and(ThreadNameHandler {
+            names: names.clone(),
+        })
+        .build_with_producer();
+
+        disruptor.start();
+        while names.lock().unwrap().len() < 2 {
+            thread::yield_now();
+        }
+        disruptor.stop();
+
+        assert!(
+            names
+                .lock()
+                .unwrap()
+                .contains(&"disruptor-journal-0".to_string())
+        );
     }
 }
@@ -0,0 +1,270 @@
+use std::{
+    sync::{
+        Arc, Mutex, OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+// Values below 2^SUB_BUCKET_BITS are recorded exactly; above that every
+// power of two is split into 2^(SUB_BUCKET_BITS - 1) linear sub-buckets,
+// which keeps the relative error under 2%.
+const SUB_BUCKET_BITS: u32 = 7;
+const SUB_BUCKET_COUNT: u64 = 1 << SUB_BUCKET_BITS;
+const SUB_BUCKET_HALF: u64 = SUB_BUCKET_COUNT / 2;
+const BUCKET_COUNT: usize =
+    (SUB_BUCKET_COUNT + (u64::BITS - SUB_BUCKET_BITS) as u64 * SUB_BUCKET_HALF) as usize;
+
+pub struct LatencyHistogram {
+    counts: Box<[AtomicU64]>,
+    max: AtomicU64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        LatencyHistogram {
+            counts: (0..BUCKET_COUNT).map(|_| AtomicU64::new(0)).collect(),
+            max: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record(&self, latency: Duration) {
+        let nanos = latency.as_nanos().min(u64::MAX as u128) as u64;
+        self.counts[Self::index_of(nanos)].fetch_add(1, Ordering::Relaxed);
+        self.max.fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.counts.iter().map(|c| c.load(Ordering::Relaxed)).sum()
+    }
+
+    pub fn max(&self) -> Duration {
+        Duration::from_nanos(self.max.load(Ordering::Relaxed))
+    }
+
+    pub fn value_at_quantile(&self, quantile: f64) -> Duration {
+        let counts: Vec<u64> = self
+            .counts
+            .iter()
+            .map(|c| c.load(Ordering::Relaxed))
+            .collect();
+        let total: u64 = counts.iter().sum();
+        if total == 0 {
+            return Duration::ZERO;
+        }
+
+        let target = (total as f64 * quantile).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (index, count) in counts.iter().enumerate() {
+            seen += count;
+            if seen >= target {
+                let highest = Self::highest_equivalent(index);
+                return Duration::from_nanos(highest.min(self.max.load(Ordering::Relaxed)));
+            }
+        }
+        self.max()
+    }
+
+    fn index_of(value: u64) -> usize {
+        if value < SUB_BUCKET_COUNT {
+            return value as usize;
+        }
+        let shift = u64::BITS - value.leading_zeros() - SUB_BUCKET_BITS;
+        let sub_bucket = (value >> shift) - SUB_BUCKET_HALF;
+        (SUB_BUCKET_COUNT + (shift as u64 - 1) * SUB_BUCKET_HALF + sub_bucket) as usize
+    }
+
+    fn highest_equivalent(index: usize) -> u64 {
+        let index = index as u64;
+        if index < SUB_BUCKET_COUNT {
+            return index;
+        }
+        let shift = (index - SUB_BUCKET_COUNT) / SUB_BUCKET_HALF + 1;
+        let sub_bucket = (index - SUB_BUCKET_COUNT) % SUB_BUCKET_HALF + SUB_BUCKET_HALF;
+        ((sub_bucket + 1) << shift).wrapping_sub(1)
+    }
+}
+
+// Publish times for each slot, indexed like the ring buffer itself.
+pub struct PublishTimestamps {
+    epoch: Instant,
+    slots: Box<[AtomicU64]>,
+    mask: usize,
+}
+
+impl PublishTimestamps {
+    pub fn new(buffer_size: usize) -> Self {
+        assert!(
+            buffer_size.is_power_of_two(),
+            "buffer size must be power of two"
+        );
+        PublishTimestamps {
+            epoch: Instant::now(),
+            slots: (0..buffer_size).map(|_| AtomicU64::new(0)).collect(),
+            mask: buffer_size - 1,
+        }
+    }
+
+    pub(crate) fn record(&self, sequence: i64, published_at: Instant) {
+        let nanos = published_at.duration_since(self.epoch).as_nanos() as u64;
+        self.slots[sequence as usize & self.mask].store(nanos, Ordering::Relaxed);
+    }
+
+    pub(crate) fn elapsed(&self, sequence: i64) -> Duration {
+        let published = self.slots[sequence as usize & self.mask].load(Ordering::Relaxed);
+        self.epoch
+            .elapsed()
+            .saturating_sub(Duration::from_nanos(published))
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct StageRecorder {
+    timestamps: Arc<PublishTimestamps>,
+    histogram: Arc<LatencyHistogram>,
+}
+
+impl StageRecorder {
+    pub(crate) fn record(&self, sequence: i64) {
+        self.histogram.record(self.timestamps.elapsed(sequence));
+    }
+}
+
+// Handed to a processor when it is registered. It stays empty, and records
+// nothing, until tracing is enabled on the builder.
+#[derive(Clone, Default)]
+pub(crate) struct LatencyStage {
+    recorder: Arc<OnceLock<StageRecorder>>,
+}
+
+impl LatencyStage {
+    pub(crate) fn record(&self, sequence: i64) {
+        if let Some(recorder) = self.recorder.get() {
+            recorder.record(sequence);
+        }
+    }
+}
+
+// Hands out a stage per processor. Stages handed out before tracing is
+// enabled are filled in, in registration order, once it is.
+#[derive(Default)]
+pub(crate) struct LatencyStages {
+    tracer: Option<Arc<LatencyTracer>>,
+    pending: Vec<(Option<(String, usize)>, LatencyStage)>,
+}
+
+impl LatencyStages {
+    pub(crate) fn enable(&mut self, buffer_size: usize) -> Arc<LatencyTracer> {
+        let tracer = self
+            .tracer
+            .get_or_insert_with(|| Arc::new(LatencyTracer::new(buffer_size)))
+            .clone();
+        for (name, stage) in self.pending.drain(..) {
+            let name = name.as_ref().map(|(name, index)| (name.as_str(), *index));
+            let _ = stage.recorder.set(tracer.stage(name));
+        }
+        tracer
+    }
+
+    pub(crate) fn stage(&mut self, name: Option<(&str, usize)>) -> LatencyStage {
+        let stage = LatencyStage::default();
+        match &self.tracer {
+            Some(tracer) => {
+                let _ = stage.recorder.set(tracer.stage(name));
+            }
+            None => {
+                let name = name.map(|(name, index)| (name.to_string(), index));
+                self.pending.push((name, stage.clone()));
+            }
+        }
+        stage
+    }
+
+    pub(crate) fn tracer(&self) -> Option<Arc<LatencyTracer>> {
+        self.tracer.clone()
+    }
+}
+
+pub struct StageLatency {
+    pub name: String,
+    pub histogram: Arc<LatencyHistogram>,
+}
+
+pub struct LatencyTracer {
+    timestamps: Arc<PublishTimestamps>,
+    stages: Mutex<Vec<(String, Arc<LatencyHistogram>)>>,
+}
+
+impl LatencyTracer {
+    pub fn new(buffer_size: usize) -> Self {
+        LatencyTracer {
+            timestamps: Arc::new(PublishTimestamps::new(buffer_size)),
+            stages: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn timestamps(&self) -> Arc<PublishTimestamps> {
+        self.timestamps.clone()
+    }
+
+    pub(crate) fn stage(&self, name: Option<(&str, usize)>) -> StageRecorder {
+        let mut stages = self.stages.lock().unwrap();
+        let name = match name {
+            Some((name, index)) => format!("{name}-{index}"),
+            None => format!("stage-{}", stages.len()),
+        };
+        let histogram = Arc::new(LatencyHistogram::new());
+        stages.push((name, histogram.clone()));
+        StageRecorder {
+            timestamps: self.timestamps.clone(),
+            histogram,
+        }
+    }
+
+    pub fn stages(&self) -> Vec<StageLatency> {
+        self.stages
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, histogram)| StageLatency {
+                name: name.clone(),
+                histogram: histogram.clone(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_quantiles_within_two_percent() {
+        let histogram = LatencyHistogram::new();
+        for micros in 1..=1000 {
+            histogram.record(Duration::from_micros(micros));
+        }
+
+        assert_eq!(histogram.count(), 1000);
+        assert_eq!(histogram.max(), Duration::from_micros(1000));
+        let p50 = histogram.value_at_quantile(0.5).as_nanos() as f64;
+        assert!((p50 - 500_000.0).abs() / 500_000.0 < 0.02);
+        let p99 = histogram.value_at_quantile(0.99).as_nanos() as f64;
+        assert!((p99 - 990_000.0).abs() / 990_000.0 < 0.02);
+    }
+
+    #[test]
+    fn test_histogram_index_round_trip() {
+        for value in [0, 1, 127, 128, 129, 255, 256, 1_000_000, u64::MAX] {
+            let index = LatencyHistogram::index_of(value);
+            assert!(index < BUCKET_COUNT);
+            assert!(LatencyHistogram::highest_equivalent(index) >= value);
+        }
+    }
+}
@@ -1,38 +1,67 @@
 pub mod adapters;
+pub mod async_batch_event_processor;
 pub mod barrier;
 pub mod batch_event_processor;
 pub mod builder;
 pub mod consumer_repository;
 pub mod disruptor;
+pub mod error;
+pub mod event_poller;
+pub mod event_stream;
+pub mod exception_handler;
 pub mod executor;
+#[cfg(feature = "latency")]
+pub mod latency;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod multi_producer;
+pub mod prometheus;
 pub mod publisher;
+pub mod rewind_strategy;
 pub mod ringbuffer;
 pub mod sequence;
 pub mod shared_ref;
 pub mod single_producer;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod try_event_handler;
 pub mod util;
 pub mod wait_strategy;
-mod work_processor;
+pub mod work_processor;
+pub mod worker_pool;
 
 pub use publisher::Publisher;
 pub use shared_ref::SharedRef;
+use std::any::Any;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub use adapters::{EventProcessorAdapter, SequencerAdapter};
+pub use async_batch_event_processor::AsyncBatchEventProcessor;
 pub use barrier::ProcessingSequenceBarrier;
 pub use batch_event_processor::BatchEventProcessor;
-pub use builder::DisruptorBuilder;
+pub use builder::{DisruptorBuilder, ExceptionHandlerSetting, HandlerHandle};
 pub use consumer_repository::ConsumerRepository;
 pub use disruptor::Disruptor;
+pub use error::{
+    BatchTooLarge, HandlerError, InsufficientCapacity, InvalidCore, RewindableError,
+    ShutdownTimeout, WaitError,
+};
 
+pub use event_poller::{EventPoller, PollState};
+pub use event_stream::{ClonedEventStream, EventRef, EventStream};
+#[cfg(feature = "metrics")]
+pub use metrics::DisruptorMetrics;
 pub use multi_producer::MultiProducer;
 pub use multi_producer::MultiProducerSequencer;
 pub use ringbuffer::RingBuffer;
 pub use sequence::Sequence;
 pub use single_producer::SingleProducer;
 pub use single_producer::SingleProducerSequencer;
+pub use try_event_handler::TryEventHandlerAdapter;
+pub use work_processor::WorkProcessor;
+pub use worker_pool::WorkerPool;
 
 const STATE_IDLE: u8 = 0u8;
 const STATE_HALTED: u8 = 1u8;
@@ -40,19 +69,31 @@ const STATE_RUNNING: u8 = 2u8;
 
 pub trait Sequencer: Send + Sync + 'static {
     fn next(&mut self, sequence: i64) -> i64;
+    fn try_next(&mut self, sequence: i64) -> Result<i64, InsufficientCapacity>;
     fn publish(&self, sequence: i64);
     fn batch_publish(&self, low: i64, high: i64);
     fn highest_published(&self, next_sequence: i64, available_sequence: i64) -> i64;
     fn available(&self, sequence: i64) -> bool;
     fn cursor(&self) -> Arc<Sequence>;
-    fn add_gating_sequence(&mut self, gating_sequence: Arc<Sequence>);
+    fn add_gating_sequence(&self, gating_sequence: Arc<Sequence>);
+    fn remove_gating_sequence(&self, gating_sequence: &Arc<Sequence>) -> bool;
+    fn gating_sequences(&self) -> Vec<Arc<Sequence>>;
     fn buffer_size(&self) -> i64;
+    fn remaining_capacity(&self) -> i64;
+    fn has_available_capacity(&self, required: i64) -> bool;
+
+    #[cfg(feature = "metrics")]
+    fn metrics(&self) -> Option<Arc<metrics::SequencerMetrics>> {
+        None
+    }
 }
 
 pub trait SequenceBarrier: Send + Sync {
-    fn wait_for(&self, sequence: i64) -> Option<i64>;
+    fn wait_for(&self, sequence: i64) -> Result<i64, WaitError>;
+    fn wait_for_async(&self, sequence: i64) -> impl Future<Output = Result<i64, WaitError>> + Send;
     fn alert(&self);
     fn clear_alert(&self);
+    fn signal_sequence_advanced(&self);
 }
 
 pub trait WaitStrategy: Send + Sync + 'static {
@@ -62,21 +103,156 @@ pub trait WaitStrategy: Send + Sync + 'static {
         cursor: Arc<Sequence>,
         dependent_sequence: Vec<Arc<Sequence>>,
         check_alert: F,
-    ) -> Option<i64>;
+    ) -> Result<i64, WaitError>;
+
+    // `highest_published` maps the lowest dependent sequence to the highest
+    // slot that can actually be read, which lags behind the cursor while a
+    // multi producer claim is still unpublished.
+    fn wait_for_async<F, P>(
+        &self,
+        sequence: i64,
+        cursor: Arc<Sequence>,
+        dependent_sequence: Vec<Arc<Sequence>>,
+        check_alert: F,
+        highest_published: P,
+    ) -> impl Future<Output = Result<i64, WaitError>> + Send
+    where
+        F: Fn() -> bool + Send,
+        P: Fn(i64) -> i64 + Send,
+    {
+        async move {
+            let available_sequence =
+                self.wait_for(sequence, cursor, dependent_sequence, check_alert)?;
+            Ok(highest_published(available_sequence))
+        }
+    }
 
     fn signal_all_when_blocking(&self);
+
+    // Called by processors after moving their sequence forward, so waiters
+    // that depend on them can be woken instead of polling.
+    fn signal_sequence_advanced(&self) {}
 }
 
+// Wait strategies whose `wait_for_async` parks on a waker rather than
+// blocking the polling thread. Streams require one, since they are polled
+// from arbitrary async code.
+pub trait AsyncCapableWaitStrategy: WaitStrategy {}
+
 pub trait EventFactory<E> {
+    #[allow(clippy::new_ret_no_self, clippy::wrong_self_convention)]
     fn new(&self) -> E;
 }
 
 pub trait EventHandler<E>: Send + Sync + 'static {
-    fn on_event(&mut self, event: &mut E, sequence: i64, end_of_batch: bool) {}
+    fn on_event(&mut self, _event: &mut E, _sequence: i64, _end_of_batch: bool) {}
+
+    fn on_event_rewindable(
+        &mut self,
+        event: &mut E,
+        sequence: i64,
+        end_of_batch: bool,
+    ) -> Result<(), RewindableError> {
+        self.on_event(event, sequence, end_of_batch);
+        Ok(())
+    }
+
+    fn on_timeout(&mut self, _sequence: i64) {}
+
+    fn on_start(&mut self) {}
+
+    fn on_shutdown(&mut self) {}
+
+    fn on_batch_start(&mut self, _batch_size: i64, _queue_depth: i64) {}
+}
+
+pub trait AsyncEventHandler<E>: Send + Sync + 'static {
+    fn on_event(
+        &mut self,
+        event: &mut E,
+        sequence: i64,
+        end_of_batch: bool,
+    ) -> impl Future<Output = ()> + Send;
+
+    fn on_timeout(&mut self, _sequence: i64) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    fn on_start(&mut self) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    fn on_shutdown(&mut self) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    fn on_batch_start(
+        &mut self,
+        _batch_size: i64,
+        _queue_depth: i64,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+}
+
+pub trait TryEventHandler<E>: Send + Sync + 'static {
+    fn on_event(
+        &mut self,
+        event: &mut E,
+        sequence: i64,
+        end_of_batch: bool,
+    ) -> Result<(), HandlerError>;
+
+    fn on_timeout(&mut self, _sequence: i64) {}
+
+    fn on_start(&mut self) {}
+
+    fn on_shutdown(&mut self) {}
+
+    fn on_batch_start(&mut self, _batch_size: i64, _queue_depth: i64) {}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExceptionAction {
+    Continue,
+    HaltProcessor,
+    HaltDisruptor,
+}
+
+pub trait ExceptionHandler<E>: Send + Sync + 'static {
+    fn handle_event_exception(
+        &self,
+        error: &(dyn Any + Send),
+        sequence: i64,
+        event: &mut E,
+    ) -> ExceptionAction;
+    fn handle_on_start_exception(&self, error: &(dyn Any + Send)) -> ExceptionAction;
+    fn handle_on_shutdown_exception(&self, error: &(dyn Any + Send));
+    // `sequence` is the last one the processor handled before timing out.
+    fn handle_on_timeout_exception(
+        &self,
+        error: &(dyn Any + Send),
+        sequence: i64,
+    ) -> ExceptionAction;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RewindAction {
+    Rewind,
+    RewindAfter(Duration),
+    GiveUp,
+}
+
+pub trait BatchRewindStrategy: Send + Sync + 'static {
+    fn handle_rewind(&self, error: &RewindableError, attempts: u32) -> RewindAction;
 }
 
 pub trait WorkHandler<E>: Send + Sync + 'static {
-    fn on_event(&mut self, event: &mut E) {}
+    fn on_event(&mut self, _event: &mut E) {}
+
+    fn on_start(&mut self) {}
+
+    fn on_shutdown(&mut self) {}
 }
 
 pub trait Runnable: Send + Sync {
@@ -87,10 +263,47 @@ pub trait EventProcessor: Runnable {
     fn sequence(&self) -> Arc<Sequence>;
     fn running(self) -> bool;
     fn halt(&mut self);
+
+    #[cfg(feature = "metrics")]
+    fn metrics(&self) -> Option<Arc<metrics::ProcessorMetrics>> {
+        None
+    }
+}
+
+pub trait JoinHandle: Send {
+    fn join(self: Box<Self>);
+    fn is_finished(&self) -> bool;
 }
 
 pub trait Executor {
-    fn execute(&self, runnable: Box<dyn Runnable>);
+    fn execute(&self, runnable: Box<dyn Runnable>) -> Box<dyn JoinHandle>;
+
+    fn execute_named(
+        &self,
+        runnable: Box<dyn Runnable>,
+        _name: &str,
+        _index: usize,
+    ) -> Box<dyn JoinHandle> {
+        self.execute(runnable)
+    }
+}
+
+impl<T> Executor for Arc<T>
+where
+    T: Executor,
+{
+    fn execute(&self, runnable: Box<dyn Runnable>) -> Box<dyn JoinHandle> {
+        (**self).execute(runnable)
+    }
+
+    fn execute_named(
+        &self,
+        runnable: Box<dyn Runnable>,
+        name: &str,
+        index: usize,
+    ) -> Box<dyn JoinHandle> {
+        (**self).execute_named(runnable, name, index)
+    }
 }
 
 pub trait Producer<E, S>
@@ -98,4 +311,5 @@ where
     S: Sequencer,
 {
     fn mut_sequencer(&mut self) -> &mut S;
+    fn mut_publisher(&mut self) -> &mut Publisher<E, S>;
 }
@@ -0,0 +1,165 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+// Batch sizes are bucketed by power of two: bucket `i` counts batches of
+// `2^i..2^(i+1)` events.
+const BATCH_SIZE_BUCKETS: usize = 64;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchSizeHistogram {
+    buckets: Vec<u64>,
+}
+
+impl BatchSizeHistogram {
+    pub fn count(&self) -> u64 {
+        self.buckets.iter().sum()
+    }
+
+    pub fn buckets(&self) -> &[u64] {
+        &self.buckets
+    }
+
+    // Upper bound of the bucket holding the given quantile, so the result
+    // over-estimates by at most a factor of two.
+    pub fn value_at_quantile(&self, quantile: f64) -> u64 {
+        let target = (self.count() as f64 * quantile).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (bucket, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= target {
+                return (1u64 << bucket).saturating_mul(2) - 1;
+            }
+        }
+        0
+    }
+}
+
+pub struct ProcessorMetrics {
+    events: AtomicU64,
+    batch_sizes: [AtomicU64; BATCH_SIZE_BUCKETS],
+}
+
+impl Default for ProcessorMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProcessorMetrics {
+    pub fn new() -> Self {
+        ProcessorMetrics {
+            events: AtomicU64::new(0),
+            batch_sizes: std::array::from_fn(|_| AtomicU64::new(0)),
+        }
+    }
+
+    pub(crate) fn record_batch(&self, batch_size: i64) {
+        self.events
+            .fetch_add(batch_size.max(1) as u64, Ordering::Relaxed);
+        self.record_batch_size(batch_size);
+    }
+
+    // Buckets a batch whose events were already counted one at a time.
+    pub(crate) fn record_batch_size(&self, batch_size: i64) {
+        let batch_size = batch_size.max(1) as u64;
+        let bucket = (u64::BITS - 1 - batch_size.leading_zeros()) as usize;
+        self.batch_sizes[bucket].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_event(&self) {
+        self.events.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn events(&self) -> u64 {
+        self.events.load(Ordering::Relaxed)
+    }
+
+    pub fn batch_sizes(&self) -> BatchSizeHistogram {
+        BatchSizeHistogram {
+            buckets: self
+                .batch_sizes
+                .iter()
+                .map(|count| count.load(Ordering::Relaxed))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SequencerMetrics {
+    claims: AtomicU64,
+    stalls: AtomicU64,
+    blocked_nanos: AtomicU64,
+}
+
+impl SequencerMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn record_claim(&self) {
+        self.claims.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_stall(&self, blocked: Duration) {
+        self.stalls.fetch_add(1, Ordering::Relaxed);
+        self.blocked_nanos
+            .fetch_add(blocked.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> SequencerSnapshot {
+        SequencerSnapshot {
+            claims: self.claims.load(Ordering::Relaxed),
+            stalls: self.stalls.load(Ordering::Relaxed),
+            blocked: Duration::from_nanos(self.blocked_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SequencerSnapshot {
+    pub claims: u64,
+    pub stalls: u64,
+    pub blocked: Duration,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProcessorSnapshot {
+    pub name: Option<String>,
+    pub index: usize,
+    pub sequence: i64,
+    pub lag: i64,
+    pub events: u64,
+    pub batch_sizes: BatchSizeHistogram,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DisruptorMetrics {
+    pub cursor: i64,
+    pub processors: Vec<ProcessorSnapshot>,
+    pub sequencer: SequencerSnapshot,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_sizes_are_bucketed_by_power_of_two() {
+        let metrics = ProcessorMetrics::new();
+        metrics.record_batch(1);
+        metrics.record_batch(3);
+        metrics.record_batch(3);
+        metrics.record_batch(100);
+
+        let histogram = metrics.batch_sizes();
+        assert_eq!(metrics.events(), 107);
+        assert_eq!(histogram.count(), 4);
+        assert_eq!(&histogram.buckets()[..3], &[1, 2, 0]);
+        assert_eq!(histogram.buckets()[6], 1);
+        assert_eq!(histogram.value_at_quantile(0.5), 3);
+        assert_eq!(histogram.value_at_quantile(1.0), 127);
+    }
+}
@@ -5,24 +5,40 @@ use std::{
 };
 
 use crate::{
-    Producer, Publisher, RingBuffer, Sequence, Sequencer, SequencerAdapter, SharedRef, util,
+    InsufficientCapacity, Producer, Publisher, RingBuffer, Sequence, Sequencer, SequencerAdapter,
+    SharedRef, WaitStrategy, sequence::SequenceGroup,
 };
 
-pub struct MultiProducerSequencer {
+#[cfg(feature = "metrics")]
+use std::time::Instant;
+
+#[cfg(feature = "metrics")]
+use crate::metrics::SequencerMetrics;
+
+pub struct MultiProducerSequencer<W>
+where
+    W: WaitStrategy,
+{
     buffer_size: i64,
     index_mask: i64,
     index_shift: i64,
     cursor: Arc<Sequence>,
     gating_sequence_cache: Sequence,
-    gating_sequences: Vec<Arc<Sequence>>,
+    gating_sequences: SequenceGroup,
     available_buffer: Box<[Sequence]>,
+    wait_strategy: Arc<W>,
+    #[cfg(feature = "metrics")]
+    metrics: Arc<SequencerMetrics>,
 }
 
-unsafe impl Send for MultiProducerSequencer {}
-unsafe impl Sync for MultiProducerSequencer {}
+unsafe impl<W> Send for MultiProducerSequencer<W> where W: WaitStrategy {}
+unsafe impl<W> Sync for MultiProducerSequencer<W> where W: WaitStrategy {}
 
-impl MultiProducerSequencer {
-    pub fn new(buffer_size: i64) -> Self {
+impl<W> MultiProducerSequencer<W>
+where
+    W: WaitStrategy,
+{
+    pub fn new(buffer_size: i64, wait_strategy: Arc<W>) -> Self {
         let available_buffer = (0..buffer_size)
             .map(|_| Sequence::new())
             .collect::<Vec<_>>()
@@ -36,8 +52,11 @@ impl MultiProducerSequencer {
             index_shift,
             cursor: Arc::new(Sequence::new()),
             gating_sequence_cache: Sequence::new(),
-            gating_sequences: vec![],
+            gating_sequences: SequenceGroup::new(),
             available_buffer,
+            wait_strategy,
+            #[cfg(feature = "metrics")]
+            metrics: Arc::new(SequencerMetrics::new()),
         }
     }
 
@@ -56,11 +75,32 @@ impl MultiProducerSequencer {
     fn calc_index(&self, sequence: i64) -> usize {
         (sequence & self.index_mask) as usize
     }
+
+    fn has_capacity_from(&self, cursor_value: i64, required: i64) -> bool {
+        let wrap_point = cursor_value + required - self.buffer_size;
+        let cached_gating_sequence = self.gating_sequence_cache.get();
+
+        if wrap_point > cached_gating_sequence || cached_gating_sequence > cursor_value {
+            let gating_sequence = self.gating_sequences.minimum_with(cursor_value);
+            self.gating_sequence_cache.set(gating_sequence);
+
+            if wrap_point > gating_sequence {
+                return false;
+            }
+        }
+
+        true
+    }
 }
 
-impl Sequencer for MultiProducerSequencer {
+impl<W> Sequencer for MultiProducerSequencer<W>
+where
+    W: WaitStrategy,
+{
     fn next(&mut self, sequence: i64) -> i64 {
         let buffer_size = self.buffer_size;
+        #[cfg(feature = "metrics")]
+        let mut blocked_since = None;
         loop {
             let current = self.cursor.get();
             let next = current + sequence;
@@ -70,9 +110,11 @@ impl Sequencer for MultiProducerSequencer {
             let cached_gating_sequence = self.gating_sequence_cache.get();
 
             if wrap_point > cached_gating_sequence || cached_gating_sequence > current {
-                let gating_sequence = util::minimum_sequence(&self.gating_sequences);
+                let gating_sequence = self.gating_sequences.minimum();
 
                 if wrap_point > gating_sequence {
+                    #[cfg(feature = "metrics")]
+                    blocked_since.get_or_insert_with(Instant::now);
                     thread::yield_now();
                     continue;
                 }
@@ -81,19 +123,45 @@ impl Sequencer for MultiProducerSequencer {
             }
 
             if self.cursor.compare_exchange_weak(current, next) {
+                #[cfg(feature = "metrics")]
+                {
+                    self.metrics.record_claim();
+                    if let Some(blocked_since) = blocked_since {
+                        self.metrics.record_stall(blocked_since.elapsed());
+                    }
+                }
                 return next;
             }
         }
     }
 
+    fn try_next(&mut self, sequence: i64) -> Result<i64, InsufficientCapacity> {
+        loop {
+            let current = self.cursor.get();
+            let next = current + sequence;
+
+            if !self.has_capacity_from(current, sequence) {
+                return Err(InsufficientCapacity);
+            }
+
+            if self.cursor.compare_exchange_weak(current, next) {
+                #[cfg(feature = "metrics")]
+                self.metrics.record_claim();
+                return Ok(next);
+            }
+        }
+    }
+
     fn publish(&self, sequence: i64) {
         self.set_available(sequence);
+        self.wait_strategy.signal_all_when_blocking();
     }
 
     fn batch_publish(&self, low: i64, high: i64) {
         for idx in low..=high {
             self.set_available(idx);
         }
+        self.wait_strategy.signal_all_when_blocking();
     }
 
     fn highest_published(&self, next_sequence: i64, available_sequence: i64) -> i64 {
@@ -117,13 +185,36 @@ impl Sequencer for MultiProducerSequencer {
         self.cursor.clone()
     }
 
-    fn add_gating_sequence(&mut self, gating_sequence: Arc<Sequence>) {
-        self.gating_sequences.push(gating_sequence);
+    fn add_gating_sequence(&self, gating_sequence: Arc<Sequence>) {
+        self.gating_sequences.add(gating_sequence);
+    }
+
+    fn remove_gating_sequence(&self, gating_sequence: &Arc<Sequence>) -> bool {
+        self.gating_sequences.remove(gating_sequence)
+    }
+
+    fn gating_sequences(&self) -> Vec<Arc<Sequence>> {
+        self.gating_sequences.to_vec()
     }
 
     fn buffer_size(&self) -> i64 {
         self.buffer_size
     }
+
+    fn remaining_capacity(&self) -> i64 {
+        let produced = self.cursor.get();
+        let consumed = self.gating_sequences.minimum_with(produced);
+        self.buffer_size - (produced - consumed)
+    }
+
+    #[cfg(feature = "metrics")]
+    fn metrics(&self) -> Option<Arc<SequencerMetrics>> {
+        Some(self.metrics.clone())
+    }
+
+    fn has_available_capacity(&self, required: i64) -> bool {
+        self.has_capacity_from(self.cursor.get(), required)
+    }
 }
 
 pub struct MultiProducer<E, S>
@@ -151,6 +242,10 @@ where
     fn mut_sequencer(&mut self) -> &mut S {
         self.publisher.mut_sequencer()
     }
+
+    fn mut_publisher(&mut self) -> &mut Publisher<E, S> {
+        &mut self.publisher
+    }
 }
 
 impl<E, S> Deref for MultiProducer<E, S>
@@ -0,0 +1,471 @@
+use std::{
+    fmt::Write,
+    sync::{Arc, Mutex},
+};
+
+#[cfg(feature = "prometheus-http")]
+use std::{
+    io::{self, Read, Write as _},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::Duration,
+};
+
+use crate::{ConsumerRepository, Sequence, Sequencer, SequencerAdapter};
+
+#[cfg(feature = "metrics")]
+use crate::metrics::ProcessorMetrics;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    Counter,
+    Gauge,
+}
+
+impl MetricKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MetricKind::Counter => "counter",
+            MetricKind::Gauge => "gauge",
+        }
+    }
+}
+
+const FAMILIES: &[(&str, MetricKind, &str)] = &[
+    (
+        "disruptor_cursor",
+        MetricKind::Gauge,
+        "Highest claimed sequence.",
+    ),
+    (
+        "disruptor_buffer_size",
+        MetricKind::Gauge,
+        "Number of slots in the ring buffer.",
+    ),
+    (
+        "disruptor_remaining_capacity",
+        MetricKind::Gauge,
+        "Slots the producer can claim without waiting.",
+    ),
+    (
+        "disruptor_gating_sequence",
+        MetricKind::Gauge,
+        "Sequences the producer is gated on.",
+    ),
+    (
+        "disruptor_processor_sequence",
+        MetricKind::Gauge,
+        "Last sequence handled by a processor.",
+    ),
+    (
+        "disruptor_processor_lag",
+        MetricKind::Gauge,
+        "Cursor minus processor sequence.",
+    ),
+    (
+        "disruptor_processor_events_total",
+        MetricKind::Counter,
+        "Events handled by a processor.",
+    ),
+    (
+        "disruptor_producer_claims_total",
+        MetricKind::Counter,
+        "Calls that claimed sequences.",
+    ),
+    (
+        "disruptor_producer_stalls_total",
+        MetricKind::Counter,
+        "Claims that had to wait for consumers.",
+    ),
+    (
+        "disruptor_producer_blocked_seconds_total",
+        MetricKind::Counter,
+        "Time producers spent waiting for consumers.",
+    ),
+];
+
+// Every sample carries its family's type and help text, so sources outside
+// this crate can add families of their own. `render` takes them from the
+// first sample of each family.
+pub struct Sample {
+    pub family: &'static str,
+    pub kind: MetricKind,
+    pub help: &'static str,
+    pub labels: Vec<(&'static str, String)>,
+    pub value: f64,
+}
+
+pub trait MetricsSource: Send + Sync {
+    fn collect(&self, samples: &mut Vec<Sample>);
+}
+
+struct ProcessorEntry {
+    label: String,
+    sequence: Arc<Sequence>,
+    #[cfg(feature = "metrics")]
+    metrics: Option<Arc<ProcessorMetrics>>,
+}
+
+// Reads the sequences a `Disruptor` already holds, so collecting never
+// touches the processors themselves. Processors can only be added through
+// the builder, so the list taken here stays complete; pollers and streams
+// attached later show up through the gating sequences.
+pub struct DisruptorExporter<S>
+where
+    S: Sequencer,
+{
+    name: String,
+    sequencer: SequencerAdapter<S>,
+    processors: Vec<ProcessorEntry>,
+}
+
+impl<S> DisruptorExporter<S>
+where
+    S: Sequencer,
+{
+    pub fn new(
+        name: &str,
+        sequencer: SequencerAdapter<S>,
+        repository: &ConsumerRepository,
+    ) -> Self {
+        let processors = repository
+            .get_processors()
+            .iter()
+            .enumerate()
+            .map(|(i, processor)| ProcessorEntry {
+                label: match processor.name() {
+                    Some((name, index)) => format!("{name}-{index}"),
+                    None => format!("processor-{i}"),
+                },
+                sequence: processor.sequence(),
+                #[cfg(feature = "metrics")]
+                metrics: processor.metrics(),
+            })
+            .collect();
+
+        DisruptorExporter {
+            name: name.to_string(),
+            sequencer,
+            processors,
+        }
+    }
+
+    fn sample(&self, family: &'static str, value: f64) -> Sample {
+        let (family, kind, help) = FAMILIES
+            .iter()
+            .find(|(name, _, _)| *name == family)
+            .copied()
+            .expect("unknown disruptor metric family");
+        Sample {
+            family,
+            kind,
+            help,
+            labels: vec![("disruptor", self.name.clone())],
+            value,
+        }
+    }
+}
+
+impl<S> MetricsSource for DisruptorExporter<S>
+where
+    S: Sequencer,
+{
+    fn collect(&self, samples: &mut Vec<Sample>) {
+        let cursor = self.sequencer.cursor().get();
+        samples.push(self.sample("disruptor_cursor", cursor as f64));
+        samples.push(self.sample("disruptor_buffer_size", self.sequencer.buffer_size() as f64));
+        samples.push(self.sample(
+            "disruptor_remaining_capacity",
+            self.sequencer.remaining_capacity() as f64,
+        ));
+
+        for (index, sequence) in self.sequencer.gating_sequences().iter().enumerate() {
+            let mut sample = self.sample("disruptor_gating_sequence", sequence.get() as f64);
+            sample.labels.push(("index", index.to_string()));
+            samples.push(sample);
+        }
+
+        for processor in self.processors.iter() {
+            let sequence = processor.sequence.get();
+            let mut sample = self.sample("disruptor_processor_sequence", sequence as f64);
+            sample.labels.push(("processor", processor.label.clone()));
+            samples.push(sample);
+
+            let mut sample = self.sample("disruptor_processor_lag", (cursor - sequence) as f64);
+            sample.labels.push(("processor", processor.label.clone()));
+            samples.push(sample);
+
+            #[cfg(feature = "metrics")]
+            if let Some(metrics) = &processor.metrics {
+                let mut sample =
+                    self.sample("disruptor_processor_events_total", metrics.events() as f64);
+                sample.labels.push(("processor", processor.label.clone()));
+                samples.push(sample);
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = self.sequencer.metrics() {
+            let snapshot = metrics.snapshot();
+            samples.push(self.sample("disruptor_producer_claims_total", snapshot.claims as f64));
+            samples.push(self.sample("disruptor_producer_stalls_total", snapshot.stalls as f64));
+            samples.push(self.sample(
+                "disruptor_producer_blocked_seconds_total",
+                snapshot.blocked.as_secs_f64(),
+            ));
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct PrometheusRegistry {
+    sources: Mutex<Vec<Box<dyn MetricsSource>>>,
+}
+
+impl PrometheusRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<M: MetricsSource + 'static>(&self, source: M) {
+        self.sources.lock().unwrap().push(Box::new(source));
+    }
+
+    pub fn render(&self) -> String {
+        let mut samples = Vec::new();
+        for source in self.sources.lock().unwrap().iter() {
+            source.collect(&mut samples);
+        }
+
+        let mut families: Vec<&'static str> = Vec::new();
+        for sample in samples.iter() {
+            if !families.contains(&sample.family) {
+                families.push(sample.family);
+            }
+        }
+
+        let mut out = String::new();
+        for family in families {
+            let mut family_samples = samples.iter().filter(|s| s.family == family).peekable();
+            if let Some(first) = family_samples.peek() {
+                let _ = writeln!(out, "# HELP {family} {}", first.help);
+                let _ = writeln!(out, "# TYPE {family} {}", first.kind.as_str());
+            }
+            for sample in family_samples {
+                out.push_str(family);
+                if !sample.labels.is_empty() {
+                    out.push('{');
+                    for (i, (name, value)) in sample.labels.iter().enumerate() {
+                        if i > 0 {
+                            out.push(',');
+                        }
+                        let _ = write!(out, "{name}=\"{}\"", escape_label(value));
+                    }
+                    out.push('}');
+                }
+                let _ = writeln!(out, " {}", sample.value);
+            }
+        }
+        out
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+// Bounds how long a silent or slow client can hold its connection thread.
+#[cfg(feature = "prometheus-http")]
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[cfg(feature = "prometheus-http")]
+pub struct MetricsServer {
+    local_addr: SocketAddr,
+    stopped: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+#[cfg(feature = "prometheus-http")]
+impl MetricsServer {
+    // Answers every request with the rendered registry; this is meant for a
+    // local scrape target, not as a general purpose HTTP server. Each
+    // connection gets its own thread, so an idle client can't hold up other
+    // scrapes or the accept loop that `shutdown` joins.
+    pub fn serve<A: ToSocketAddrs>(registry: Arc<PrometheusRegistry>, addr: A) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let stopped_flag = stopped.clone();
+        let handle = thread::Builder::new()
+            .name("disruptor-metrics".to_string())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    if stopped_flag.load(Ordering::Acquire) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        let registry = registry.clone();
+                        let _ = thread::Builder::new()
+                            .name("disruptor-metrics-conn".to_string())
+                            .spawn(move || respond(stream, &registry));
+                    }
+                }
+            })?;
+
+        Ok(MetricsServer {
+            local_addr,
+            stopped,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    pub fn shutdown(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            self.stopped.store(true, Ordering::Release);
+            // Wake the blocking accept so the thread sees the flag.
+            let _ = TcpStream::connect(self.local_addr);
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(feature = "prometheus-http")]
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(feature = "prometheus-http")]
+fn respond(mut stream: TcpStream, registry: &PrometheusRegistry) -> io::Result<()> {
+    stream.set_read_timeout(Some(CONNECTION_TIMEOUT))?;
+    stream.set_write_timeout(Some(CONNECTION_TIMEOUT))?;
+
+    // The request itself is ignored; read what arrived so the client sees a
+    // clean close rather than a reset.
+    let mut request = [0u8; 1024];
+    let _ = stream.read(&mut request)?;
+
+    let body = registry.render();
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{NoopHandler, test_builder};
+
+    #[test]
+    fn test_render_reports_sequences_and_lag() {
+        let (disruptor, mut producer) = test_builder()
+            .handler_named("journal", NoopHandler)
+            .build_with_producer();
+
+        let registry = PrometheusRegistry::new();
+        registry.register(disruptor.exporter("orders"));
+        producer.publish_batch(3, |_, e| *e = 1).unwrap();
+
+        let text = registry.render();
+        assert!(text.contains("# TYPE disruptor_cursor gauge\n"));
+        assert!(text.contains("disruptor_cursor{disruptor=\"orders\"} 2\n"));
+        assert!(text.contains("disruptor_remaining_capacity{disruptor=\"orders\"} 5\n"));
+        assert!(text.contains("disruptor_gating_sequence{disruptor=\"orders\",index=\"0\"} -1\n"));
+        assert!(
+            text.contains(
+                "disruptor_processor_lag{disruptor=\"orders\",processor=\"journal-0\"} 3\n"
+            )
+        );
+    }
+
+    struct QueueDepth;
+
+    impl MetricsSource for QueueDepth {
+        fn collect(&self, samples: &mut Vec<Sample>) {
+            samples.push(Sample {
+                family: "app_queue_depth",
+                kind: MetricKind::Gauge,
+                help: "Requests waiting to be published.",
+                labels: Vec::new(),
+                value: 4.0,
+            });
+        }
+    }
+
+    #[test]
+    fn test_render_includes_custom_families() {
+        let registry = PrometheusRegistry::new();
+        registry.register(QueueDepth);
+
+        assert_eq!(
+            registry.render(),
+            "# HELP app_queue_depth Requests waiting to be published.\n\
+             # TYPE app_queue_depth gauge\n\
+             app_queue_depth 4\n"
+        );
+    }
+
+    #[test]
+    fn test_escape_label() {
+        assert_eq!(escape_label("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+
+    #[cfg(feature = "prometheus-http")]
+    #[test]
+    fn test_server_serves_rendered_registry() {
+        let (disruptor, _producer) = test_builder().handler(NoopHandler).build_with_producer();
+
+        let registry = Arc::new(PrometheusRegistry::new());
+        registry.register(disruptor.exporter("orders"));
+        let server = MetricsServer::serve(registry, "127.0.0.1:0").unwrap();
+
+        let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+        stream
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains(
+            "disruptor_processor_lag{disruptor=\"orders\",processor=\"processor-0\"} 0\n"
+        ));
+        server.shutdown();
+    }
+
+    #[cfg(feature = "prometheus-http")]
+    #[test]
+    fn test_idle_connection_does_not_block_scrapes_or_shutdown() {
+        let registry = Arc::new(PrometheusRegistry::new());
+        let server = MetricsServer::serve(registry, "127.0.0.1:0").unwrap();
+        let _idle = TcpStream::connect(server.local_addr()).unwrap();
+
+        let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+        stream.write_all(b"GET /metrics HTTP/1.1\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+
+        let started = std::time::Instant::now();
+        server.shutdown();
+        assert!(started.elapsed() < CONNECTION_TIMEOUT);
+    }
+}
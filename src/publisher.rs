@@ -1,6 +1,9 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Instant};
 
-use crate::{RingBuffer, Sequencer, SequencerAdapter};
+use crate::{BatchTooLarge, InsufficientCapacity, RingBuffer, Sequencer, SequencerAdapter};
+
+#[cfg(feature = "latency")]
+use crate::latency::PublishTimestamps;
 
 pub struct Publisher<E, S>
 where
@@ -8,6 +11,8 @@ where
 {
     sequencer: SequencerAdapter<S>,
     ringbuffer: Arc<RingBuffer<E>>,
+    #[cfg(feature = "latency")]
+    timestamps: Option<Arc<PublishTimestamps>>,
 }
 
 unsafe impl<E, S> Send for Publisher<E, S> where S: Sequencer {}
@@ -21,12 +26,51 @@ where
         Self {
             sequencer,
             ringbuffer,
+            #[cfg(feature = "latency")]
+            timestamps: None,
+        }
+    }
+
+    #[cfg(feature = "latency")]
+    pub fn set_publish_timestamps(&mut self, timestamps: Arc<PublishTimestamps>) {
+        self.timestamps = Some(timestamps);
+    }
+
+    // The clock is read before claiming so time blocked on a full ring counts
+    // towards the latency, but it can only be stored once the slot is ours.
+    #[cfg(feature = "latency")]
+    fn now(&self) -> Option<Instant> {
+        self.timestamps.as_ref().map(|_| Instant::now())
+    }
+
+    #[cfg(not(feature = "latency"))]
+    fn now(&self) -> Option<Instant> {
+        None
+    }
+
+    #[cfg(not(feature = "latency"))]
+    fn stamp(&self, _low: i64, _high: i64, _published_at: Option<Instant>) {}
+
+    #[cfg(feature = "latency")]
+    fn stamp(&self, low: i64, high: i64, published_at: Option<Instant>) {
+        if let (Some(timestamps), Some(published_at)) = (&self.timestamps, published_at) {
+            for sequence in low..=high {
+                timestamps.record(sequence, published_at);
+            }
         }
     }
 
     pub fn mut_sequencer(&mut self) -> &mut S {
         &mut self.sequencer
     }
+
+    pub fn remaining_capacity(&self) -> i64 {
+        self.sequencer.remaining_capacity()
+    }
+
+    pub fn has_available_capacity(&self, required: i64) -> bool {
+        self.sequencer.has_available_capacity(required)
+    }
 }
 
 impl<E, S> Publisher<E, S>
@@ -37,9 +81,188 @@ where
     where
         F: Fn(&mut E),
     {
+        let published_at = self.now();
         let sequence = self.sequencer.next(1);
         let event = self.ringbuffer.get_mut(sequence as usize);
         f(event);
+        self.stamp(sequence, sequence, published_at);
         self.sequencer.publish(sequence);
     }
+
+    pub fn try_publish<F>(&mut self, f: F) -> Result<(), InsufficientCapacity>
+    where
+        F: Fn(&mut E),
+    {
+        let published_at = self.now();
+        let sequence = self.sequencer.try_next(1)?;
+        let event = self.ringbuffer.get_mut(sequence as usize);
+        f(event);
+        self.stamp(sequence, sequence, published_at);
+        self.sequencer.publish(sequence);
+        Ok(())
+    }
+
+    pub fn publish_batch<F>(&mut self, n: usize, f: F) -> Result<(), BatchTooLarge>
+    where
+        F: Fn(i64, &mut E),
+    {
+        if n == 0 {
+            return Ok(());
+        }
+        self.check_batch_size(n)?;
+
+        let published_at = self.now();
+        let high = self.sequencer.next(n as i64);
+        let low = high - (n as i64 - 1);
+        for sequence in low..=high {
+            let event = self.ringbuffer.get_mut(sequence as usize);
+            f(sequence, event);
+        }
+        self.stamp(low, high, published_at);
+        self.sequencer.batch_publish(low, high);
+        Ok(())
+    }
+
+    pub fn publish_iter<I>(&mut self, iter: I) -> Result<(), BatchTooLarge>
+    where
+        I: ExactSizeIterator<Item = E>,
+    {
+        let n = iter.len();
+        if n == 0 {
+            return Ok(());
+        }
+        self.check_batch_size(n)?;
+
+        let published_at = self.now();
+        let high = self.sequencer.next(n as i64);
+        let low = high - (n as i64 - 1);
+        // The whole range is claimed up front, so it is published even if the
+        // iterator yields fewer items than it reported.
+        for (sequence, item) in (low..=high).zip(iter) {
+            *self.ringbuffer.get_mut(sequence as usize) = item;
+        }
+        self.stamp(low, high, published_at);
+        self.sequencer.batch_publish(low, high);
+        Ok(())
+    }
+
+    fn check_batch_size(&self, n: usize) -> Result<(), BatchTooLarge> {
+        let buffer_size = self.sequencer.buffer_size() as usize;
+        if n > buffer_size {
+            return Err(BatchTooLarge {
+                size: n,
+                buffer_size,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        MultiProducerSequencer, Sequence, SingleProducerSequencer, test_support::TestFactory,
+        wait_strategy::BusySpinWaitStrategy,
+    };
+
+    fn publisher<S: Sequencer>(sequencer: S, consumer: Arc<Sequence>) -> Publisher<i64, S> {
+        let sequencer = SequencerAdapter::new(sequencer);
+        sequencer.add_gating_sequence(consumer);
+        let ringbuffer = Arc::new(RingBuffer::new(4, TestFactory));
+        Publisher::new(sequencer, ringbuffer)
+    }
+
+    #[test]
+    fn test_single_producer_try_publish_rejects_when_full() {
+        let consumer = Arc::new(Sequence::new());
+        let sequencer = SingleProducerSequencer::new(4, Arc::new(BusySpinWaitStrategy {}));
+        let mut publisher = publisher(sequencer, consumer.clone());
+
+        for i in 0..4 {
+            assert!(publisher.try_publish(|e| *e = i).is_ok());
+        }
+        assert_eq!(publisher.try_publish(|e| *e = 4), Err(InsufficientCapacity));
+
+        consumer.set(0);
+        assert!(publisher.try_publish(|e| *e = 4).is_ok());
+    }
+
+    #[test]
+    fn test_multi_producer_try_publish_rejects_when_full() {
+        let consumer = Arc::new(Sequence::new());
+        let sequencer = MultiProducerSequencer::new(4, Arc::new(BusySpinWaitStrategy {}));
+        let mut publisher = publisher(sequencer, consumer.clone());
+
+        for i in 0..4 {
+            assert!(publisher.try_publish(|e| *e = i).is_ok());
+        }
+        assert_eq!(publisher.try_publish(|e| *e = 4), Err(InsufficientCapacity));
+
+        consumer.set(0);
+        assert!(publisher.try_publish(|e| *e = 4).is_ok());
+    }
+
+    #[test]
+    fn test_remaining_capacity_tracks_slowest_consumer() {
+        let consumer = Arc::new(Sequence::new());
+        let sequencer = MultiProducerSequencer::new(4, Arc::new(BusySpinWaitStrategy {}));
+        let mut publisher = publisher(sequencer, consumer.clone());
+
+        assert_eq!(publisher.remaining_capacity(), 4);
+        publisher.publish(|e| *e = 1);
+        publisher.publish(|e| *e = 2);
+        publisher.publish(|e| *e = 3);
+        assert_eq!(publisher.remaining_capacity(), 1);
+        assert!(publisher.has_available_capacity(1));
+        assert!(!publisher.has_available_capacity(2));
+
+        consumer.set(1);
+        assert_eq!(publisher.remaining_capacity(), 3);
+        assert!(publisher.has_available_capacity(3));
+    }
+
+    #[test]
+    fn test_publish_batch_claims_contiguous_range() {
+        let consumer = Arc::new(Sequence::new());
+        let sequencer = MultiProducerSequencer::new(4, Arc::new(BusySpinWaitStrategy {}));
+        let cursor = sequencer.cursor();
+        let mut publisher = publisher(sequencer, consumer);
+
+        publisher.publish_batch(3, |seq, e| *e = seq * 10).unwrap();
+
+        assert_eq!(cursor.get(), 2);
+        assert_eq!(*publisher.ringbuffer.get(2), 20);
+        assert!((0..3).all(|seq| publisher.sequencer.available(seq)));
+    }
+
+    #[test]
+    fn test_publish_iter_writes_items() {
+        let consumer = Arc::new(Sequence::new());
+        let sequencer = SingleProducerSequencer::new(4, Arc::new(BusySpinWaitStrategy {}));
+        let cursor = sequencer.cursor();
+        let mut publisher = publisher(sequencer, consumer);
+
+        publisher.publish_iter(vec![7, 8, 9].into_iter()).unwrap();
+
+        assert_eq!(cursor.get(), 2);
+        assert_eq!(*publisher.ringbuffer.get(0), 7);
+        assert_eq!(*publisher.ringbuffer.get(2), 9);
+    }
+
+    #[test]
+    fn test_publish_batch_rejects_oversized_batch() {
+        let consumer = Arc::new(Sequence::new());
+        let sequencer = MultiProducerSequencer::new(4, Arc::new(BusySpinWaitStrategy {}));
+        let mut publisher = publisher(sequencer, consumer);
+
+        assert_eq!(
+            publisher.publish_batch(5, |_, _| {}),
+            Err(BatchTooLarge {
+                size: 5,
+                buffer_size: 4
+            })
+        );
+        assert!(publisher.publish_iter(vec![0; 5].into_iter()).is_err());
+    }
 }
@@ -0,0 +1,76 @@
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use crate::{BatchRewindStrategy, RewindAction, RewindableError};
+
+pub struct SimpleBatchRewindStrategy;
+
+impl BatchRewindStrategy for SimpleBatchRewindStrategy {
+    fn handle_rewind(&self, _error: &RewindableError, _attempts: u32) -> RewindAction {
+        RewindAction::Rewind
+    }
+}
+
+pub struct EventuallyGiveUpBatchRewindStrategy {
+    max_attempts: u32,
+}
+
+impl EventuallyGiveUpBatchRewindStrategy {
+    pub fn new(max_attempts: u32) -> Self {
+        EventuallyGiveUpBatchRewindStrategy { max_attempts }
+    }
+}
+
+impl BatchRewindStrategy for EventuallyGiveUpBatchRewindStrategy {
+    fn handle_rewind(&self, _error: &RewindableError, attempts: u32) -> RewindAction {
+        if attempts > self.max_attempts {
+            RewindAction::GiveUp
+        } else {
+            RewindAction::Rewind
+        }
+    }
+}
+
+pub struct BackoffBatchRewindStrategy {
+    pause: Duration,
+}
+
+impl BackoffBatchRewindStrategy {
+    pub fn new(pause: Duration) -> Self {
+        BackoffBatchRewindStrategy { pause }
+    }
+}
+
+// The processor awaits the pause, so it doesn't block an executor thread.
+impl BatchRewindStrategy for BackoffBatchRewindStrategy {
+    fn handle_rewind(&self, _error: &RewindableError, _attempts: u32) -> RewindAction {
+        RewindAction::RewindAfter(self.pause)
+    }
+}
+
+// Lets the builder hand processors a strategy it can still change, so
+// `with_rewind_strategy` reaches handlers registered before the call.
+pub(crate) struct RewindStrategyWrapper {
+    delegate: RwLock<Arc<dyn BatchRewindStrategy>>,
+}
+
+impl RewindStrategyWrapper {
+    pub(crate) fn new() -> Self {
+        RewindStrategyWrapper {
+            delegate: RwLock::new(Arc::new(SimpleBatchRewindStrategy)),
+        }
+    }
+
+    pub(crate) fn switch_to(&self, rewind_strategy: Arc<dyn BatchRewindStrategy>) {
+        *self.delegate.write().unwrap() = rewind_strategy;
+    }
+}
+
+impl BatchRewindStrategy for RewindStrategyWrapper {
+    fn handle_rewind(&self, error: &RewindableError, attempts: u32) -> RewindAction {
+        let delegate = self.delegate.read().unwrap().clone();
+        delegate.handle_rewind(error, attempts)
+    }
+}
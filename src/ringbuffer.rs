@@ -36,14 +36,13 @@ impl<E> RingBuffer<E> {
 impl<E> RingBuffer<E> {
     pub fn get(&self, sequence: usize) -> &E {
         let index = sequence & self.mask;
-        let data = unsafe { &*(*self.buffer.get_unchecked(index).get()).assume_init_ref() };
-        data
+        unsafe { (*self.buffer.get_unchecked(index).get()).assume_init_ref() }
     }
 
+    #[allow(clippy::mut_from_ref)]
     pub fn get_mut(&self, sequence: usize) -> &mut E {
         let index = sequence & self.mask;
-        let data = unsafe { &mut *(*self.buffer.get_unchecked(index).get()).assume_init_mut() };
-        data
+        unsafe { (*self.buffer.get_unchecked(index).get()).assume_init_mut() }
     }
 }
 
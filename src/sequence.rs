@@ -1,10 +1,23 @@
-use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{
+    Arc,
+    atomic::{AtomicI64, Ordering},
+};
+
+use arc_swap::ArcSwap;
+
+use crate::util;
 
 #[repr(align(64))]
 pub struct Sequence {
     value: AtomicI64,
 }
 
+impl Default for Sequence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Sequence {
     pub fn new() -> Self {
         Sequence {
@@ -26,3 +39,48 @@ impl Sequence {
             .is_ok()
     }
 }
+
+// Copy-on-write list of gating sequences. Producers read a snapshot on every
+// claim, and adding or removing swaps in a new list, so sequences can be
+// attached while producers are running.
+#[derive(Default)]
+pub(crate) struct SequenceGroup {
+    sequences: ArcSwap<Vec<Arc<Sequence>>>,
+}
+
+impl SequenceGroup {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn add(&self, sequence: Arc<Sequence>) {
+        self.sequences.rcu(|sequences| {
+            let mut sequences = Vec::clone(sequences);
+            sequences.push(sequence.clone());
+            sequences
+        });
+    }
+
+    pub(crate) fn remove(&self, sequence: &Arc<Sequence>) -> bool {
+        let previous = self.sequences.rcu(|sequences| {
+            sequences
+                .iter()
+                .filter(|s| !Arc::ptr_eq(s, sequence))
+                .cloned()
+                .collect::<Vec<_>>()
+        });
+        previous.iter().any(|s| Arc::ptr_eq(s, sequence))
+    }
+
+    pub(crate) fn minimum(&self) -> i64 {
+        util::minimum_sequence(&self.sequences.load())
+    }
+
+    pub(crate) fn minimum_with(&self, minimum: i64) -> i64 {
+        util::minimum_sequence_with(&self.sequences.load(), minimum)
+    }
+
+    pub(crate) fn to_vec(&self) -> Vec<Arc<Sequence>> {
+        Vec::clone(&self.sequences.load())
+    }
+}
@@ -20,7 +20,7 @@ impl<T> SharedRef<T> {
     pub fn new(c: T) -> Self {
         SharedRef {
             counter: NonNull::from(Box::leak(Box::new(Counter {
-                c: c,
+                c,
                 count: AtomicUsize::new(1),
             }))),
         }
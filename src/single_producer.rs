@@ -4,35 +4,59 @@ use std::{
     thread,
 };
 
-use crate::{Producer, Publisher, RingBuffer, Sequence, Sequencer, SequencerAdapter, util};
+use crate::{
+    InsufficientCapacity, Producer, Publisher, RingBuffer, Sequence, Sequencer, SequencerAdapter,
+    WaitStrategy, sequence::SequenceGroup,
+};
+
+#[cfg(feature = "metrics")]
+use std::time::Instant;
+
+#[cfg(feature = "metrics")]
+use crate::metrics::SequencerMetrics;
 
-pub struct SingleProducerSequencer {
+pub struct SingleProducerSequencer<W>
+where
+    W: WaitStrategy,
+{
     buffer_size: i64,
     next_value: i64,
     cached_value: i64,
     cursor: Arc<Sequence>,
-    gating_sequences: Vec<Arc<Sequence>>,
+    gating_sequences: SequenceGroup,
+    wait_strategy: Arc<W>,
+    #[cfg(feature = "metrics")]
+    metrics: Arc<SequencerMetrics>,
 }
 
-unsafe impl Send for SingleProducerSequencer {}
+unsafe impl<W> Send for SingleProducerSequencer<W> where W: WaitStrategy {}
 
-impl SingleProducerSequencer {
-    pub fn new(buffer_size: i64) -> Self {
+impl<W> SingleProducerSequencer<W>
+where
+    W: WaitStrategy,
+{
+    pub fn new(buffer_size: i64, wait_strategy: Arc<W>) -> Self {
         Self {
             buffer_size,
             next_value: -1,
             cached_value: -1,
             cursor: Arc::new(Sequence::new()),
-            gating_sequences: vec![],
+            gating_sequences: SequenceGroup::new(),
+            wait_strategy,
+            #[cfg(feature = "metrics")]
+            metrics: Arc::new(SequencerMetrics::new()),
         }
     }
 
     fn min_gating_sequence(&self) -> i64 {
-        util::minimum_sequence(&self.gating_sequences)
+        self.gating_sequences.minimum()
     }
 }
 
-impl Sequencer for SingleProducerSequencer {
+impl<W> Sequencer for SingleProducerSequencer<W>
+where
+    W: WaitStrategy,
+{
     fn next(&mut self, sequence: i64) -> i64 {
         let next_value = self.next_value;
 
@@ -44,26 +68,58 @@ impl Sequencer for SingleProducerSequencer {
             self.cursor.set(next_value);
 
             let mut min_sequence = self.min_gating_sequence();
-            while wrap_point > min_sequence {
-                thread::yield_now();
-                min_sequence = self.min_gating_sequence();
+            if wrap_point > min_sequence {
+                #[cfg(feature = "metrics")]
+                let blocked_since = Instant::now();
+                while wrap_point > min_sequence {
+                    thread::yield_now();
+                    min_sequence = self.min_gating_sequence();
+                }
+                #[cfg(feature = "metrics")]
+                self.metrics.record_stall(blocked_since.elapsed());
             }
             self.cached_value = min_sequence;
         }
 
+        #[cfg(feature = "metrics")]
+        self.metrics.record_claim();
         self.next_value = next_sequence;
         next_sequence
     }
 
+    fn try_next(&mut self, sequence: i64) -> Result<i64, InsufficientCapacity> {
+        let next_value = self.next_value;
+
+        let next_sequence = next_value + sequence;
+        let wrap_point = next_sequence - self.buffer_size;
+
+        let cached_gate = self.cached_value;
+        if wrap_point > cached_gate || cached_gate > next_value {
+            self.cursor.set(next_value);
+
+            let min_sequence = self.min_gating_sequence();
+            self.cached_value = min_sequence;
+            if wrap_point > min_sequence {
+                return Err(InsufficientCapacity);
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        self.metrics.record_claim();
+        self.next_value = next_sequence;
+        Ok(next_sequence)
+    }
+
     fn publish(&self, sequence: i64) {
         self.cursor.set(sequence);
+        self.wait_strategy.signal_all_when_blocking();
     }
 
-    fn batch_publish(&self, low: i64, high: i64) {
+    fn batch_publish(&self, _low: i64, high: i64) {
         self.publish(high);
     }
 
-    fn highest_published(&self, next_sequence: i64, available_sequence: i64) -> i64 {
+    fn highest_published(&self, _next_sequence: i64, available_sequence: i64) -> i64 {
         available_sequence
     }
 
@@ -75,13 +131,40 @@ impl Sequencer for SingleProducerSequencer {
         self.cursor.clone()
     }
 
-    fn add_gating_sequence(&mut self, gating_sequence: Arc<Sequence>) {
-        self.gating_sequences.push(gating_sequence);
+    fn add_gating_sequence(&self, gating_sequence: Arc<Sequence>) {
+        self.gating_sequences.add(gating_sequence);
+    }
+
+    fn remove_gating_sequence(&self, gating_sequence: &Arc<Sequence>) -> bool {
+        self.gating_sequences.remove(gating_sequence)
+    }
+
+    fn gating_sequences(&self) -> Vec<Arc<Sequence>> {
+        self.gating_sequences.to_vec()
     }
 
     fn buffer_size(&self) -> i64 {
         self.buffer_size
     }
+
+    // Read from the cursor, not `next_value`, since other threads call this
+    // while the producer claims.
+    fn remaining_capacity(&self) -> i64 {
+        let produced = self.cursor.get();
+        let consumed = self.gating_sequences.minimum_with(produced);
+        self.buffer_size - (produced - consumed)
+    }
+
+    #[cfg(feature = "metrics")]
+    fn metrics(&self) -> Option<Arc<SequencerMetrics>> {
+        Some(self.metrics.clone())
+    }
+
+    fn has_available_capacity(&self, required: i64) -> bool {
+        let produced = self.cursor.get();
+        let wrap_point = produced + required - self.buffer_size;
+        wrap_point <= self.gating_sequences.minimum_with(produced)
+    }
 }
 
 pub struct SingleProducer<E, S>
@@ -114,6 +197,10 @@ where
     fn mut_sequencer(&mut self) -> &mut S {
         self.publisher.mut_sequencer()
     }
+
+    fn mut_publisher(&mut self) -> &mut Publisher<E, S> {
+        &mut self.publisher
+    }
 }
 
 impl<E, S> Deref for SingleProducer<E, S>
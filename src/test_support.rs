@@ -0,0 +1,63 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicI64, Ordering},
+    },
+    time::Duration,
+};
+
+use crate::{
+    DisruptorBuilder, EventFactory, EventHandler, SingleProducer, SingleProducerSequencer,
+    WorkHandler, executor::TokioExecutor, wait_strategy::BlockingWaitStrategy,
+};
+
+// Fixtures shared by the test modules across the crate.
+
+pub(crate) const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub(crate) struct TestFactory;
+
+impl EventFactory<i64> for TestFactory {
+    fn new(&self) -> i64 {
+        0
+    }
+}
+
+pub(crate) struct SumHandler {
+    pub(crate) sum: Arc<AtomicI64>,
+}
+
+impl EventHandler<i64> for SumHandler {
+    fn on_event(&mut self, event: &mut i64, _sequence: i64, _end_of_batch: bool) {
+        self.sum.fetch_add(*event, Ordering::Relaxed);
+    }
+}
+
+impl WorkHandler<i64> for SumHandler {
+    fn on_event(&mut self, event: &mut i64) {
+        self.sum.fetch_add(*event, Ordering::Relaxed);
+    }
+}
+
+pub(crate) struct NoopHandler;
+
+impl EventHandler<i64> for NoopHandler {}
+
+pub(crate) type TestBuilder = DisruptorBuilder<
+    i64,
+    TokioExecutor,
+    BlockingWaitStrategy,
+    SingleProducerSequencer<BlockingWaitStrategy>,
+    SingleProducer<i64, SingleProducerSequencer<BlockingWaitStrategy>>,
+>;
+
+// Eight slots, one Tokio worker and a blocking wait strategy, which is all
+// most tests need.
+pub(crate) fn test_builder() -> TestBuilder {
+    DisruptorBuilder::new_single_producer(
+        8,
+        TestFactory,
+        TokioExecutor::new(1),
+        BlockingWaitStrategy::new(),
+    )
+}
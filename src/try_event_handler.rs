@@ -0,0 +1,252 @@
+use std::{marker::PhantomData, time::Duration};
+
+use futures_timer::Delay;
+
+use crate::{AsyncEventHandler, HandlerError, TryEventHandler};
+
+pub trait DeadLetterSink<E>: Send + Sync + 'static {
+    fn send(&mut self, event: &E, sequence: i64, error: HandlerError);
+}
+
+impl<E, F> DeadLetterSink<E> for F
+where
+    F: FnMut(&E, i64, HandlerError) + Send + Sync + 'static,
+{
+    fn send(&mut self, event: &E, sequence: i64, error: HandlerError) {
+        self(event, sequence, error)
+    }
+}
+
+pub struct TryEventHandlerAdapter<E, T>
+where
+    T: TryEventHandler<E>,
+{
+    handler: T,
+    retries: u32,
+    backoff: Duration,
+    dead_letter: Option<Box<dyn DeadLetterSink<E>>>,
+    _marker: PhantomData<fn(&mut E)>,
+}
+
+impl<E, T> TryEventHandlerAdapter<E, T>
+where
+    T: TryEventHandler<E>,
+{
+    pub fn new(handler: T) -> Self {
+        TryEventHandlerAdapter {
+            handler,
+            retries: 0,
+            backoff: Duration::ZERO,
+            dead_letter: None,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn retry(mut self, retries: u32, backoff: Duration) -> Self {
+        self.retries = retries;
+        self.backoff = backoff;
+        self
+    }
+
+    pub fn dead_letter<D: DeadLetterSink<E>>(mut self, sink: D) -> Self {
+        self.dead_letter = Some(Box::new(sink));
+        self
+    }
+}
+
+impl<E, T> From<T> for TryEventHandlerAdapter<E, T>
+where
+    T: TryEventHandler<E>,
+{
+    fn from(handler: T) -> Self {
+        TryEventHandlerAdapter::new(handler)
+    }
+}
+
+// Runs on the async processor so a retry backoff is awaited rather than
+// blocking the executor thread.
+impl<E, T> AsyncEventHandler<E> for TryEventHandlerAdapter<E, T>
+where
+    E: Send + 'static,
+    T: TryEventHandler<E>,
+{
+    async fn on_event(&mut self, event: &mut E, sequence: i64, end_of_batch: bool) {
+        let mut attempt = 0;
+        loop {
+            let error = match self.handler.on_event(event, sequence, end_of_batch) {
+                Ok(()) => return,
+                Err(error) => error,
+            };
+
+            if attempt < self.retries {
+                attempt += 1;
+                if !self.backoff.is_zero() {
+                    Delay::new(self.backoff).await;
+                }
+                continue;
+            }
+
+            match self.dead_letter.as_mut() {
+                Some(sink) => sink.send(event, sequence, error),
+                None => log::error!(
+                    "skipping sequence {} after {} attempts: {}",
+                    sequence,
+                    attempt + 1,
+                    error
+                ),
+            }
+            return;
+        }
+    }
+
+    async fn on_timeout(&mut self, sequence: i64) {
+        self.handler.on_timeout(sequence);
+    }
+
+    async fn on_start(&mut self) {
+        self.handler.on_start();
+    }
+
+    async fn on_shutdown(&mut self) {
+        self.handler.on_shutdown();
+    }
+
+    async fn on_batch_start(&mut self, batch_size: i64, queue_depth: i64) {
+        self.handler.on_batch_start(batch_size, queue_depth);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::*;
+    use crate::{
+        DisruptorBuilder,
+        executor::TokioExecutor,
+        test_support::{SHUTDOWN_TIMEOUT, TestFactory},
+        util::block_on,
+        wait_strategy::AsyncWaitStrategy,
+    };
+
+    struct FlakyHandler {
+        failures_left: u32,
+        handled: Vec<i64>,
+    }
+
+    impl TryEventHandler<i64> for FlakyHandler {
+        fn on_event(
+            &mut self,
+            event: &mut i64,
+            _sequence: i64,
+            _end_of_batch: bool,
+        ) -> Result<(), HandlerError> {
+            if self.failures_left > 0 {
+                self.failures_left -= 1;
+                return Err(HandlerError::new("store unavailable"));
+            }
+            self.handled.push(*event);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_retry_until_success() {
+        let mut adapter = TryEventHandlerAdapter::new(FlakyHandler {
+            failures_left: 2,
+            handled: vec![],
+        })
+        .retry(2, Duration::from_millis(1));
+
+        block_on(adapter.on_event(&mut 7, 0, true));
+
+        assert_eq!(adapter.handler.handled, vec![7]);
+    }
+
+    #[test]
+    fn test_dead_letter_after_retries_exhausted() {
+        let dead_letters = Arc::new(Mutex::new(Vec::new()));
+        let sink = {
+            let dead_letters = dead_letters.clone();
+            move |event: &i64, sequence: i64, error: HandlerError| {
+                dead_letters
+                    .lock()
+                    .unwrap()
+                    .push((*event, sequence, error.to_string()));
+            }
+        };
+        let mut adapter = TryEventHandlerAdapter::new(FlakyHandler {
+            failures_left: 3,
+            handled: vec![],
+        })
+        .retry(1, Duration::ZERO)
+        .dead_letter(sink);
+
+        block_on(adapter.on_event(&mut 7, 4, true));
+        block_on(adapter.on_event(&mut 8, 5, true));
+
+        assert_eq!(
+            *dead_letters.lock().unwrap(),
+            vec![(7, 4, "store unavailable".to_string())]
+        );
+        assert_eq!(adapter.handler.handled, vec![8]);
+    }
+
+    struct CountingHandler {
+        count: Arc<AtomicUsize>,
+    }
+
+    impl TryEventHandler<i64> for CountingHandler {
+        fn on_event(
+            &mut self,
+            _event: &mut i64,
+            _sequence: i64,
+            _end_of_batch: bool,
+        ) -> Result<(), HandlerError> {
+            self.count.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_handler_try_registers_with_builder() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let dead_letters = Arc::new(Mutex::new(Vec::new()));
+        let sink = {
+            let dead_letters = dead_letters.clone();
+            move |event: &i64, _sequence: i64, _error: HandlerError| {
+                dead_letters.lock().unwrap().push(*event);
+            }
+        };
+        let (mut disruptor, mut producer) = DisruptorBuilder::new_single_producer(
+            8,
+            TestFactory,
+            TokioExecutor::new(1),
+            AsyncWaitStrategy::new(),
+        )
+        .handler_try(
+            TryEventHandlerAdapter::new(FlakyHandler {
+                failures_left: 3,
+                handled: vec![],
+            })
+            .retry(1, Duration::from_millis(5))
+            .dead_letter(sink),
+        )
+        .then_try(CountingHandler {
+            count: count.clone(),
+        })
+        .build_with_producer();
+
+        disruptor.start();
+        for i in 0..4 {
+            producer.publish(|e| *e = i);
+        }
+
+        assert!(disruptor.shutdown_timeout(SHUTDOWN_TIMEOUT).is_ok());
+        assert_eq!(*dead_letters.lock().unwrap(), vec![0]);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+}
@@ -1,7 +1,55 @@
-use std::sync::Arc;
+use std::{
+    any::Any,
+    future::poll_fn,
+    panic::{self, AssertUnwindSafe},
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
 
 use crate::Sequence;
 
-pub(crate) fn minimum_sequence(sequences: &Vec<Arc<Sequence>>) -> i64 {
+pub(crate) fn minimum_sequence(sequences: &[Arc<Sequence>]) -> i64 {
     sequences.iter().map(|s| s.get()).min().unwrap_or(0)
 }
+
+pub(crate) fn minimum_sequence_with(sequences: &[Arc<Sequence>], minimum: i64) -> i64 {
+    sequences.iter().map(|s| s.get()).fold(minimum, i64::min)
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        thread::park();
+    }
+}
+
+pub(crate) async fn catch_unwind<F: Future>(future: F) -> Result<F::Output, Box<dyn Any + Send>> {
+    let mut future = pin!(future);
+    poll_fn(
+        |cx| match panic::catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(cx))) {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(error) => Poll::Ready(Err(error)),
+        },
+    )
+    .await
+}
@@ -1,6 +1,14 @@
-use std::{sync::Arc, thread};
+use std::{
+    future,
+    sync::{Arc, Condvar, Mutex},
+    task::{Poll, Waker},
+    thread,
+    time::{Duration, Instant},
+};
 
-use crate::{Sequence, WaitStrategy, util};
+use atomic_waker::AtomicWaker;
+
+use crate::{AsyncCapableWaitStrategy, Sequence, WaitError, WaitStrategy, util};
 
 pub struct BusySpinWaitStrategy;
 
@@ -11,16 +19,16 @@ impl WaitStrategy for BusySpinWaitStrategy {
         _cursor: Arc<Sequence>,
         dependent_sequence: Vec<Arc<Sequence>>,
         check_alert: F,
-    ) -> Option<i64> {
+    ) -> Result<i64, WaitError> {
         let mut available_sequence = util::minimum_sequence(&dependent_sequence);
         while available_sequence < sequence {
             if check_alert() {
-                return None;
+                return Err(WaitError::Alerted);
             }
             available_sequence = util::minimum_sequence(&dependent_sequence);
             std::hint::spin_loop();
         }
-        Some(available_sequence)
+        Ok(available_sequence)
     }
 
     fn signal_all_when_blocking(&self) {}
@@ -35,17 +43,579 @@ impl WaitStrategy for YieldingWaitStrategy {
         _cursor: Arc<Sequence>,
         dependent_sequence: Vec<Arc<Sequence>>,
         check_alert: F,
-    ) -> Option<i64> {
+    ) -> Result<i64, WaitError> {
         let mut available_sequence = util::minimum_sequence(&dependent_sequence);
         while available_sequence < sequence {
             if check_alert() {
-                return None;
+                return Err(WaitError::Alerted);
             }
             available_sequence = util::minimum_sequence(&dependent_sequence);
             thread::yield_now();
         }
-        Some(available_sequence)
+        Ok(available_sequence)
+    }
+
+    fn signal_all_when_blocking(&self) {}
+}
+
+pub struct BlockingWaitStrategy {
+    mutex: Mutex<()>,
+    condvar: Condvar,
+}
+
+impl BlockingWaitStrategy {
+    pub fn new() -> Self {
+        BlockingWaitStrategy {
+            mutex: Mutex::new(()),
+            condvar: Condvar::new(),
+        }
+    }
+}
+
+impl Default for BlockingWaitStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WaitStrategy for BlockingWaitStrategy {
+    fn wait_for<F: Fn() -> bool>(
+        &self,
+        sequence: i64,
+        cursor: Arc<Sequence>,
+        dependent_sequence: Vec<Arc<Sequence>>,
+        check_alert: F,
+    ) -> Result<i64, WaitError> {
+        if cursor.get() < sequence {
+            let mut guard = self.mutex.lock().unwrap();
+            while cursor.get() < sequence {
+                if check_alert() {
+                    return Err(WaitError::Alerted);
+                }
+                guard = self.condvar.wait(guard).unwrap();
+            }
+        }
+
+        let mut available_sequence = util::minimum_sequence(&dependent_sequence);
+        while available_sequence < sequence {
+            if check_alert() {
+                return Err(WaitError::Alerted);
+            }
+            available_sequence = util::minimum_sequence(&dependent_sequence);
+            std::hint::spin_loop();
+        }
+        Ok(available_sequence)
+    }
+
+    fn signal_all_when_blocking(&self) {
+        let _guard = self.mutex.lock().unwrap();
+        self.condvar.notify_all();
+    }
+}
+
+pub struct TimeoutBlockingWaitStrategy {
+    mutex: Mutex<()>,
+    condvar: Condvar,
+    timeout: Duration,
+}
+
+impl TimeoutBlockingWaitStrategy {
+    pub fn new(timeout: Duration) -> Self {
+        TimeoutBlockingWaitStrategy {
+            mutex: Mutex::new(()),
+            condvar: Condvar::new(),
+            timeout,
+        }
+    }
+}
+
+impl WaitStrategy for TimeoutBlockingWaitStrategy {
+    fn wait_for<F: Fn() -> bool>(
+        &self,
+        sequence: i64,
+        cursor: Arc<Sequence>,
+        dependent_sequence: Vec<Arc<Sequence>>,
+        check_alert: F,
+    ) -> Result<i64, WaitError> {
+        if cursor.get() < sequence {
+            let deadline = Instant::now() + self.timeout;
+            let mut guard = self.mutex.lock().unwrap();
+            while cursor.get() < sequence {
+                if check_alert() {
+                    return Err(WaitError::Alerted);
+                }
+
+                let now = Instant::now();
+                if now >= deadline {
+                    return Err(WaitError::Timeout);
+                }
+                guard = self.condvar.wait_timeout(guard, deadline - now).unwrap().0;
+            }
+        }
+
+        let mut available_sequence = util::minimum_sequence(&dependent_sequence);
+        while available_sequence < sequence {
+            if check_alert() {
+                return Err(WaitError::Alerted);
+            }
+            available_sequence = util::minimum_sequence(&dependent_sequence);
+            std::hint::spin_loop();
+        }
+        Ok(available_sequence)
+    }
+
+    fn signal_all_when_blocking(&self) {
+        let _guard = self.mutex.lock().unwrap();
+        self.condvar.notify_all();
+    }
+}
+
+pub struct SleepingWaitStrategy {
+    spin_tries: u32,
+    yield_tries: u32,
+    sleep: Duration,
+}
+
+impl SleepingWaitStrategy {
+    pub fn new(spin_tries: u32, yield_tries: u32, sleep: Duration) -> Self {
+        SleepingWaitStrategy {
+            spin_tries,
+            yield_tries,
+            sleep,
+        }
+    }
+}
+
+impl Default for SleepingWaitStrategy {
+    fn default() -> Self {
+        Self::new(100, 100, Duration::from_nanos(100))
+    }
+}
+
+impl WaitStrategy for SleepingWaitStrategy {
+    fn wait_for<F: Fn() -> bool>(
+        &self,
+        sequence: i64,
+        _cursor: Arc<Sequence>,
+        dependent_sequence: Vec<Arc<Sequence>>,
+        check_alert: F,
+    ) -> Result<i64, WaitError> {
+        let mut counter = 0u32;
+        let mut available_sequence = util::minimum_sequence(&dependent_sequence);
+        while available_sequence < sequence {
+            if check_alert() {
+                return Err(WaitError::Alerted);
+            }
+
+            if counter < self.spin_tries {
+                counter += 1;
+                std::hint::spin_loop();
+            } else if counter < self.spin_tries.saturating_add(self.yield_tries) {
+                counter += 1;
+                thread::yield_now();
+            } else {
+                thread::sleep(self.sleep);
+            }
+
+            available_sequence = util::minimum_sequence(&dependent_sequence);
+        }
+        Ok(available_sequence)
     }
 
     fn signal_all_when_blocking(&self) {}
 }
+
+const PHASED_BACKOFF_SPIN_TRIES: u32 = 10000;
+
+pub struct PhasedBackoffWaitStrategy<W>
+where
+    W: WaitStrategy,
+{
+    spin_timeout: Duration,
+    yield_timeout: Duration,
+    fallback: W,
+}
+
+impl<W> PhasedBackoffWaitStrategy<W>
+where
+    W: WaitStrategy,
+{
+    pub fn new(spin_timeout: Duration, yield_timeout: Duration, fallback: W) -> Self {
+        PhasedBackoffWaitStrategy {
+            spin_timeout,
+            yield_timeout: spin_timeout + yield_timeout,
+            fallback,
+        }
+    }
+}
+
+impl PhasedBackoffWaitStrategy<BlockingWaitStrategy> {
+    pub fn with_blocking(spin_timeout: Duration, yield_timeout: Duration) -> Self {
+        Self::new(spin_timeout, yield_timeout, BlockingWaitStrategy::new())
+    }
+}
+
+impl PhasedBackoffWaitStrategy<SleepingWaitStrategy> {
+    pub fn with_sleeping(spin_timeout: Duration, yield_timeout: Duration) -> Self {
+        Self::new(
+            spin_timeout,
+            yield_timeout,
+            SleepingWaitStrategy::new(0, 0, Duration::from_nanos(100)),
+        )
+    }
+}
+
+impl<W> WaitStrategy for PhasedBackoffWaitStrategy<W>
+where
+    W: WaitStrategy,
+{
+    fn wait_for<F: Fn() -> bool>(
+        &self,
+        sequence: i64,
+        cursor: Arc<Sequence>,
+        dependent_sequence: Vec<Arc<Sequence>>,
+        check_alert: F,
+    ) -> Result<i64, WaitError> {
+        let mut start: Option<Instant> = None;
+        let mut counter = 0u32;
+        loop {
+            if check_alert() {
+                return Err(WaitError::Alerted);
+            }
+
+            let available_sequence = util::minimum_sequence(&dependent_sequence);
+            if available_sequence >= sequence {
+                return Ok(available_sequence);
+            }
+
+            counter += 1;
+            if counter > PHASED_BACKOFF_SPIN_TRIES {
+                let elapsed = start.get_or_insert_with(Instant::now).elapsed();
+                if elapsed > self.yield_timeout {
+                    return self.fallback.wait_for(
+                        sequence,
+                        cursor,
+                        dependent_sequence,
+                        check_alert,
+                    );
+                } else if elapsed > self.spin_timeout {
+                    thread::yield_now();
+                }
+                counter = 0;
+            }
+        }
+    }
+
+    fn signal_all_when_blocking(&self) {
+        self.fallback.signal_all_when_blocking();
+    }
+
+    fn signal_sequence_advanced(&self) {
+        self.fallback.signal_sequence_advanced();
+    }
+}
+
+pub struct AsyncWaitStrategy {
+    wakers: Mutex<Vec<Arc<AtomicWaker>>>,
+}
+
+impl AsyncWaitStrategy {
+    pub fn new() -> Self {
+        AsyncWaitStrategy {
+            wakers: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn wake_all(&self) {
+        for waker in self.wakers.lock().unwrap().iter() {
+            waker.wake();
+        }
+    }
+}
+
+impl Default for AsyncWaitStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct WakerRegistration<'a> {
+    wakers: &'a Mutex<Vec<Arc<AtomicWaker>>>,
+    waker: Arc<AtomicWaker>,
+}
+
+impl<'a> WakerRegistration<'a> {
+    fn new(wakers: &'a Mutex<Vec<Arc<AtomicWaker>>>) -> Self {
+        let waker = Arc::new(AtomicWaker::new());
+        wakers.lock().unwrap().push(waker.clone());
+        WakerRegistration { wakers, waker }
+    }
+
+    fn register(&self, waker: &Waker) {
+        self.waker.register(waker);
+    }
+}
+
+impl Drop for WakerRegistration<'_> {
+    fn drop(&mut self) {
+        self.wakers
+            .lock()
+            .unwrap()
+            .retain(|waker| !Arc::ptr_eq(waker, &self.waker));
+    }
+}
+
+impl AsyncCapableWaitStrategy for AsyncWaitStrategy {}
+
+impl WaitStrategy for AsyncWaitStrategy {
+    fn wait_for<F: Fn() -> bool>(
+        &self,
+        sequence: i64,
+        _cursor: Arc<Sequence>,
+        dependent_sequence: Vec<Arc<Sequence>>,
+        check_alert: F,
+    ) -> Result<i64, WaitError> {
+        let mut available_sequence = util::minimum_sequence(&dependent_sequence);
+        while available_sequence < sequence {
+            if check_alert() {
+                return Err(WaitError::Alerted);
+            }
+            available_sequence = util::minimum_sequence(&dependent_sequence);
+            thread::yield_now();
+        }
+        Ok(available_sequence)
+    }
+
+    async fn wait_for_async<F, P>(
+        &self,
+        sequence: i64,
+        cursor: Arc<Sequence>,
+        dependent_sequence: Vec<Arc<Sequence>>,
+        check_alert: F,
+        highest_published: P,
+    ) -> Result<i64, WaitError>
+    where
+        F: Fn() -> bool + Send,
+        P: Fn(i64) -> i64 + Send,
+    {
+        let available_sequence = util::minimum_sequence(&dependent_sequence);
+        if available_sequence >= sequence {
+            let highest = highest_published(available_sequence);
+            if highest >= sequence {
+                return Ok(highest);
+            }
+        }
+
+        // The waker is registered before every check, so a publish or an
+        // upstream processor advancing in between still wakes this task.
+        let registration = WakerRegistration::new(&self.wakers);
+        future::poll_fn(move |cx| {
+            registration.register(cx.waker());
+
+            if check_alert() {
+                return Poll::Ready(Err(WaitError::Alerted));
+            }
+
+            if cursor.get() < sequence {
+                return Poll::Pending;
+            }
+
+            let available_sequence = util::minimum_sequence(&dependent_sequence);
+            if available_sequence < sequence {
+                return Poll::Pending;
+            }
+
+            let highest = highest_published(available_sequence);
+            if highest < sequence {
+                return Poll::Pending;
+            }
+
+            Poll::Ready(Ok(highest))
+        })
+        .await
+    }
+
+    fn signal_all_when_blocking(&self) {
+        self.wake_all();
+    }
+
+    fn signal_sequence_advanced(&self) {
+        self.wake_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        pin::pin,
+        sync::atomic::{AtomicBool, AtomicI64, AtomicUsize, Ordering},
+        task::{Context, Wake},
+        time::Duration,
+    };
+
+    #[test]
+    fn test_blocking_wakes_on_signal() {
+        let strategy = Arc::new(BlockingWaitStrategy::new());
+        let cursor = Arc::new(Sequence::new());
+
+        let waiter = {
+            let strategy = strategy.clone();
+            let cursor = cursor.clone();
+            thread::spawn(move || strategy.wait_for(0, cursor.clone(), vec![cursor], || false))
+        };
+
+        thread::sleep(Duration::from_millis(20));
+        cursor.set(0);
+        strategy.signal_all_when_blocking();
+
+        assert_eq!(waiter.join().unwrap(), Ok(0));
+    }
+
+    #[test]
+    fn test_blocking_returns_none_on_alert() {
+        let strategy = Arc::new(BlockingWaitStrategy::new());
+        let cursor = Arc::new(Sequence::new());
+        let alert = Arc::new(AtomicBool::new(false));
+
+        let waiter = {
+            let strategy = strategy.clone();
+            let cursor = cursor.clone();
+            let alert = alert.clone();
+            thread::spawn(move || {
+                strategy.wait_for(0, cursor.clone(), vec![cursor], || {
+                    alert.load(Ordering::Acquire)
+                })
+            })
+        };
+
+        thread::sleep(Duration::from_millis(20));
+        alert.store(true, Ordering::Release);
+        strategy.signal_all_when_blocking();
+
+        assert_eq!(waiter.join().unwrap(), Err(WaitError::Alerted));
+    }
+
+    #[test]
+    fn test_timeout_blocking_times_out() {
+        let strategy = TimeoutBlockingWaitStrategy::new(Duration::from_millis(10));
+        let cursor = Arc::new(Sequence::new());
+
+        let result = strategy.wait_for(0, cursor.clone(), vec![cursor], || false);
+
+        assert_eq!(result, Err(WaitError::Timeout));
+    }
+
+    #[test]
+    fn test_phased_backoff_falls_back_and_wakes_on_signal() {
+        let strategy = Arc::new(PhasedBackoffWaitStrategy::with_blocking(
+            Duration::from_micros(10),
+            Duration::from_micros(10),
+        ));
+        let cursor = Arc::new(Sequence::new());
+
+        let waiter = {
+            let strategy = strategy.clone();
+            let cursor = cursor.clone();
+            thread::spawn(move || strategy.wait_for(0, cursor.clone(), vec![cursor], || false))
+        };
+
+        thread::sleep(Duration::from_millis(20));
+        cursor.set(0);
+        strategy.signal_all_when_blocking();
+
+        assert_eq!(waiter.join().unwrap(), Ok(0));
+    }
+
+    #[test]
+    fn test_async_wakes_on_signal() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let strategy = Arc::new(AsyncWaitStrategy::new());
+        let cursor = Arc::new(Sequence::new());
+
+        let waiter = {
+            let strategy = strategy.clone();
+            let cursor = cursor.clone();
+            rt.spawn(async move {
+                strategy
+                    .wait_for_async(0, cursor.clone(), vec![cursor], || false, |s| s)
+                    .await
+            })
+        };
+
+        let result = rt.block_on(async {
+            tokio::task::yield_now().await;
+            cursor.set(0);
+            strategy.signal_all_when_blocking();
+            waiter.await.unwrap()
+        });
+
+        assert_eq!(result, Ok(0));
+        assert!(strategy.wakers.lock().unwrap().is_empty());
+    }
+
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_async_parks_until_dependents_and_publisher_signal() {
+        let strategy = AsyncWaitStrategy::new();
+        let cursor = Arc::new(Sequence::new());
+        let upstream = Arc::new(Sequence::new());
+        let published = Arc::new(AtomicI64::new(-1));
+        cursor.set(0);
+
+        let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let waker = Waker::from(counter.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let mut future = pin!(strategy.wait_for_async(
+            0,
+            cursor.clone(),
+            vec![upstream.clone()],
+            || false,
+            |available| available.min(published.load(Ordering::Acquire)),
+        ));
+
+        // A lagging dependent parks the task rather than waking it again.
+        assert!(future.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(counter.0.load(Ordering::Relaxed), 0);
+
+        upstream.set(0);
+        strategy.signal_sequence_advanced();
+        assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+
+        // Claimed but not yet published still parks.
+        assert!(future.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+
+        published.store(0, Ordering::Release);
+        strategy.signal_all_when_blocking();
+        assert_eq!(counter.0.load(Ordering::Relaxed), 2);
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Ready(Ok(0)));
+    }
+
+    #[test]
+    fn test_sleeping_backs_off_until_published() {
+        let strategy = Arc::new(SleepingWaitStrategy::new(10, 10, Duration::from_millis(1)));
+        let cursor = Arc::new(Sequence::new());
+
+        let waiter = {
+            let strategy = strategy.clone();
+            let cursor = cursor.clone();
+            thread::spawn(move || strategy.wait_for(3, cursor.clone(), vec![cursor], || false))
+        };
+
+        thread::sleep(Duration::from_millis(20));
+        cursor.set(5);
+
+        assert_eq!(waiter.join().unwrap(), Ok(5));
+    }
+}
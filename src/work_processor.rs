@@ -1,4 +1,5 @@
 use std::{
+    panic::{self, AssertUnwindSafe},
     pin::Pin,
     sync::{
         Arc,
@@ -7,10 +8,16 @@ use std::{
 };
 
 use crate::{
-    EventProcessor, RingBuffer, Runnable, STATE_HALTED, STATE_IDLE, STATE_RUNNING, Sequence,
-    SequenceBarrier, WorkHandler,
+    EventProcessor, ExceptionAction, ExceptionHandler, RingBuffer, Runnable, STATE_HALTED,
+    STATE_IDLE, STATE_RUNNING, Sequence, SequenceBarrier, WaitError, WorkHandler,
+    exception_handler::{HaltGroup, HaltProcessorExceptionHandler},
 };
 
+#[cfg(feature = "latency")]
+use crate::latency::LatencyStage;
+#[cfg(feature = "metrics")]
+use crate::metrics::ProcessorMetrics;
+
 pub struct WorkProcessor<E, B, H>
 where
     B: SequenceBarrier,
@@ -22,7 +29,13 @@ where
     work_handler: H,
     ringbuffer: Arc<RingBuffer<E>>,
     sequence_barrier: Arc<B>,
-    state: AtomicU8,
+    state: Arc<AtomicU8>,
+    #[cfg(feature = "latency")]
+    latency: LatencyStage,
+    #[cfg(feature = "metrics")]
+    metrics: Arc<ProcessorMetrics>,
+    exception_handler: Arc<dyn ExceptionHandler<E>>,
+    halt_group: HaltGroup,
 }
 
 unsafe impl<E, B, H> Send for WorkProcessor<E, B, H>
@@ -45,7 +58,7 @@ impl<E, B, H> WorkProcessor<E, B, H>
 where
     B: SequenceBarrier,
     H: WorkHandler<E>,
-    E: Send + Sync,
+    E: Send + Sync + 'static,
 {
     pub fn new(
         work_sequence: Arc<Sequence>,
@@ -59,7 +72,61 @@ where
             work_handler,
             ringbuffer,
             sequence_barrier,
-            state: AtomicU8::new(STATE_IDLE),
+            state: Arc::new(AtomicU8::new(STATE_IDLE)),
+            #[cfg(feature = "latency")]
+            latency: LatencyStage::default(),
+            #[cfg(feature = "metrics")]
+            metrics: Arc::new(ProcessorMetrics::new()),
+            exception_handler: Arc::new(HaltProcessorExceptionHandler),
+            halt_group: HaltGroup::new(),
+        }
+    }
+
+    pub fn set_exception_handler(&mut self, exception_handler: Arc<dyn ExceptionHandler<E>>) {
+        self.exception_handler = exception_handler;
+    }
+
+    #[cfg(feature = "latency")]
+    pub(crate) fn trace_latency(&mut self, latency: LatencyStage) {
+        self.latency = latency;
+    }
+
+    pub(crate) fn join_halt_group(&mut self, halt_group: &HaltGroup)
+    where
+        B: 'static,
+    {
+        let state = self.state.clone();
+        let sequence_barrier = self.sequence_barrier.clone();
+        halt_group.add(move || {
+            state.store(STATE_HALTED, Ordering::Release);
+            sequence_barrier.alert();
+        });
+        self.halt_group = halt_group.clone();
+    }
+
+    fn notify_start(&mut self) {
+        let work_handler = &mut self.work_handler;
+        if let Err(error) = panic::catch_unwind(AssertUnwindSafe(|| work_handler.on_start())) {
+            let action = self.exception_handler.handle_on_start_exception(&*error);
+            self.handle_exception(action);
+        }
+    }
+
+    fn notify_shutdown(&mut self) {
+        let work_handler = &mut self.work_handler;
+        if let Err(error) = panic::catch_unwind(AssertUnwindSafe(|| work_handler.on_shutdown())) {
+            self.exception_handler.handle_on_shutdown_exception(&*error);
+        }
+    }
+
+    fn handle_exception(&mut self, action: ExceptionAction) {
+        match action {
+            ExceptionAction::Continue => {}
+            ExceptionAction::HaltProcessor => self.halt(),
+            ExceptionAction::HaltDisruptor => {
+                self.halt();
+                self.halt_group.halt_all();
+            }
         }
     }
 }
@@ -68,7 +135,7 @@ impl<E, B, H> Runnable for WorkProcessor<E, B, H>
 where
     B: SequenceBarrier,
     H: WorkHandler<E>,
-    E: Send + Sync,
+    E: Send + Sync + 'static,
 {
     fn run(&mut self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
         Box::pin(async move {
@@ -83,17 +150,22 @@ where
                 .is_ok()
             {
                 self.sequence_barrier.clear_alert();
+                self.notify_start();
 
                 let mut processed_sequence = true;
                 let mut cached_available_sequence = -1;
                 let mut next_sequence = self.sequence.get();
+                // Work processors claim one event at a time, so a batch is
+                // the run of events handled between two waits.
+                #[cfg(feature = "metrics")]
+                let mut run = 0;
 
                 while self.state.load(Ordering::Acquire) == STATE_RUNNING {
                     if processed_sequence {
                         processed_sequence = false;
                         loop {
                             next_sequence = self.work_sequence.get() + 1;
-                            self.sequence.set(next_sequence);
+                            self.sequence.set(next_sequence - 1);
 
                             if self
                                 .work_sequence
@@ -102,21 +174,54 @@ where
                                 break;
                             }
                         }
+                        self.sequence_barrier.signal_sequence_advanced();
                     }
 
                     if cached_available_sequence >= next_sequence {
                         let event = self.ringbuffer.get_mut(next_sequence as usize);
-                        self.work_handler.on_event(event);
+                        let work_handler = &mut self.work_handler;
+                        match panic::catch_unwind(AssertUnwindSafe(|| work_handler.on_event(event)))
+                        {
+                            Ok(()) => {
+                                #[cfg(feature = "latency")]
+                                self.latency.record(next_sequence);
+                                #[cfg(feature = "metrics")]
+                                {
+                                    self.metrics.record_event();
+                                    run += 1;
+                                }
+                            }
+                            Err(error) => {
+                                // The sequence still trails the failed slot, so a
+                                // halt leaves it unprocessed like a batch processor.
+                                let action = self.exception_handler.handle_event_exception(
+                                    &*error,
+                                    next_sequence,
+                                    event,
+                                );
+                                self.handle_exception(action);
+                            }
+                        }
                         processed_sequence = true;
                     } else {
-                        if let Some(s) = self.sequence_barrier.wait_for(next_sequence) {
-                            cached_available_sequence = s;
-                        } else {
-                            break;
+                        #[cfg(feature = "metrics")]
+                        if run > 0 {
+                            self.metrics.record_batch_size(run);
+                            run = 0;
+                        }
+                        match self.sequence_barrier.wait_for_async(next_sequence).await {
+                            Ok(s) => cached_available_sequence = s,
+                            Err(WaitError::Timeout) => {}
+                            Err(WaitError::Alerted) => break,
                         }
                     }
                 }
+                #[cfg(feature = "metrics")]
+                if run > 0 {
+                    self.metrics.record_batch_size(run);
+                }
 
+                self.notify_shutdown();
                 self.state.store(STATE_IDLE, Ordering::Release);
             }
         })
@@ -141,4 +246,9 @@ where
     fn running(self) -> bool {
         self.state.load(Ordering::Acquire) == STATE_RUNNING
     }
+
+    #[cfg(feature = "metrics")]
+    fn metrics(&self) -> Option<Arc<ProcessorMetrics>> {
+        Some(self.metrics.clone())
+    }
 }
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use crate::{
+    EventProcessor, RingBuffer, Sequence, SequenceBarrier, WorkHandler,
+    work_processor::WorkProcessor,
+};
+
+pub struct WorkerPool<E, B, H>
+where
+    B: SequenceBarrier,
+    H: WorkHandler<E>,
+    E: Send + Sync,
+{
+    work_sequence: Arc<Sequence>,
+    processors: Vec<WorkProcessor<E, B, H>>,
+}
+
+impl<E, B, H> WorkerPool<E, B, H>
+where
+    B: SequenceBarrier,
+    H: WorkHandler<E>,
+    E: Send + Sync + 'static,
+{
+    pub fn new<I>(handlers: I, ringbuffer: Arc<RingBuffer<E>>, sequence_barrier: Arc<B>) -> Self
+    where
+        I: IntoIterator<Item = H>,
+    {
+        let work_sequence = Arc::new(Sequence::new());
+        let processors: Vec<_> = handlers
+            .into_iter()
+            .map(|handler| {
+                WorkProcessor::new(
+                    Arc::clone(&work_sequence),
+                    handler,
+                    Arc::clone(&ringbuffer),
+                    Arc::clone(&sequence_barrier),
+                )
+            })
+            .collect();
+
+        assert!(
+            !processors.is_empty(),
+            "worker pool needs at least one handler"
+        );
+
+        WorkerPool {
+            work_sequence,
+            processors,
+        }
+    }
+
+    pub fn work_sequence(&self) -> Arc<Sequence> {
+        self.work_sequence.clone()
+    }
+
+    pub fn worker_sequences(&self) -> Vec<Arc<Sequence>> {
+        self.processors.iter().map(|p| p.sequence()).collect()
+    }
+
+    pub fn into_processors(self) -> Vec<WorkProcessor<E, B, H>> {
+        self.processors
+    }
+}